@@ -0,0 +1,150 @@
+//! # 测试用AMM程序
+//! 
+//! 模拟外部常数乘积池的 `initialize_pool` 接口，供预测市场的毕业迁移在本地测试中调用
+//! 只记录注入的代币与SOL数量，不提供交易功能，不要部署到正式网络
+
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+
+declare_id!("AhR7LCSbrT4yCmXZr1Y8PGQFNFEd3VmyhtfDHEhegJYt");
+
+/// 池状态PDA种子（按代币mint派生）
+pub const POOL: &str = "pool";
+
+/// 池代币金库PDA种子（按代币mint派生）
+pub const POOL_VAULT: &str = "pool_vault";
+
+/// 测试用AMM程序主模块
+#[program]
+pub mod mock_amm {
+    use super::*;
+
+    /// 创建池子
+    /// 
+    /// 从出资方转入代币与SOL作为初始储备，每个mint只能建池一次
+    /// 
+    /// # 参数
+    /// * `ctx` - 指令上下文
+    /// * `token_amount` - 注入的代币数量
+    /// * `sol_amount` - 注入的SOL数量（lamports）
+    /// 
+    /// # 返回
+    /// * `Result<()>` - 操作结果
+    pub fn initialize_pool(
+        ctx: Context<InitializePool>,
+        token_amount: u64,
+        sol_amount: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .handler(token_amount, sol_amount, ctx.bumps.pool)
+    }
+}
+
+/// 池状态账户
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct Pool {
+    /// 代币mint
+    pub mint: Pubkey,
+    /// 池代币金库
+    pub token_vault: Pubkey,
+    /// 代币储备
+    pub token_reserve: u64,
+    /// SOL储备（lamports）
+    pub sol_reserve: u64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// 账户集合：建池所需账户
+/// 
+/// 前五个账户的顺序与预测市场 `migrate` 的CPI一致，其余账户由调用方透传
+#[derive(Accounts)]
+pub struct InitializePool<'info> {
+    /// 出资方（提供代币与SOL）
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// 出资方的代币账户
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = payer,
+    )]
+    pub payer_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// 代币mint
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// 代币/系统程序
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+
+    /// 池状态（PDA）
+    #[account(
+        init,
+        payer = rent_payer,
+        space = 8 + Pool::INIT_SPACE,
+        seeds = [POOL.as_bytes(), &mint.key().to_bytes()],
+        bump,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// 池代币金库（PDA代币账户，由池状态持有）
+    #[account(
+        init,
+        payer = rent_payer,
+        seeds = [POOL_VAULT.as_bytes(), &mint.key().to_bytes()],
+        bump,
+        token::mint = mint,
+        token::authority = pool,
+    )]
+    pub pool_token_vault: Box<Account<'info, TokenAccount>>,
+
+    /// 租金支付者
+    #[account(mut)]
+    pub rent_payer: Signer<'info>,
+}
+
+impl InitializePool<'_> {
+    /// 转入初始储备并记录池状态
+    pub fn handler(&mut self, token_amount: u64, sol_amount: u64, pool_bump: u8) -> Result<()> {
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.payer_token_account.to_account_info(),
+                    to: self.pool_token_vault.to_account_info(),
+                    authority: self.payer.to_account_info(),
+                },
+            ),
+            token_amount,
+        )?;
+        system_program::transfer(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: self.payer.to_account_info(),
+                    to: self.pool.to_account_info(),
+                },
+            ),
+            sol_amount,
+        )?;
+
+        let pool = &mut self.pool;
+        pool.mint = self.mint.key();
+        pool.token_vault = self.pool_token_vault.key();
+        pool.token_reserve = token_amount;
+        pool.sol_reserve = sol_amount;
+        pool.bump = pool_bump;
+
+        msg!(
+            "InitializePool: mint={}, token_amount={}, sol_amount={}",
+            pool.mint,
+            token_amount,
+            sol_amount
+        );
+
+        Ok(())
+    }
+}
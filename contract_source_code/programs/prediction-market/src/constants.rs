@@ -12,9 +12,27 @@ pub const GLOBAL: &str = "global";
 /// 市场PDA种子
 pub const MARKET: &str = "market";
 
+/// YES代币mint PDA种子（与市场ID共同派生）
+pub const YES_MINT: &str = "yes";
+
+/// NO代币mint PDA种子（与市场ID共同派生）
+pub const NO_MINT: &str = "no";
+
 /// 用户信息PDA种子
 pub const USERINFO: &str = "userinfo";
 
+/// LP份额代币mint PDA种子（按市场派生）
+pub const LP_MINT: &str = "lp_mint";
+
+/// LP份额代币精度（与SOL一致，1份额≈1 lamport）
+pub const LP_MINT_DECIMALS: u8 = 9;
+
+/// 创建者保证金托管PDA种子（按市场派生）
+pub const CREATOR_BOND: &str = "creator_bond";
+
+/// 迁移托管PDA种子（按市场派生，作为外部AMM建池CPI的签名者）
+pub const MIGRATION: &str = "migration";
+
 /// 代币元数据PDA种子
 pub const METADATA: &str = "metadata";
 
@@ -28,3 +46,23 @@ pub const NO_NAME: &str = "disagree";
 /// 每个槽位约400毫秒
 pub const MAX_START_SLOT_DELAY: u64 = 1_512_000; // ~1 week in slots (400ms each)
 
+/// 最大开始时间延迟（1周，以Unix秒计算）
+pub const MAX_START_TIME_DELAY: i64 = 604_800; // 1 week in seconds
+
+/// 平台手续费收款方数量上限
+pub const MAX_FEE_RECIPIENTS: usize = 5;
+
+/// 手续费基点分母（10000 = 100%）
+pub const FEE_BASIS_POINTS: u64 = 10_000;
+
+/// 外部AMM建池指令判别符（Anchor风格：sha256("global:initialize_pool") 前8字节）
+pub const AMM_INITIALIZE_POOL_DISCRIMINATOR: [u8; 8] = [95, 180, 10, 172, 84, 174, 232, 40];
+
+/// LP占比的定点精度（1e12 = 100%）
+pub const LP_SHARE_PRECISION: u64 = 1_000_000_000_000;
+
+/// LP手续费每份额累加器的定点精度
+pub const FEE_PER_SHARE_PRECISION: u128 = 1_000_000_000_000;
+
+/// 无效结算结果（市场被判定为无效，创建者保证金被罚没）
+pub const INVALID_OUTCOME: u8 = 2;
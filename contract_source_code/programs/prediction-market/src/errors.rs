@@ -218,4 +218,59 @@ pub enum PredictionMarketError {
     /// 当非流动性提供者尝试提取流动性时触发
     #[msg("The withdraw: not lp error.")]
     WITHDRAWNOTLPERROR,
+
+    /// 市场流动性不足错误
+    /// 当市场SOL流动性未达到配置的最小值时触发
+    #[msg("Market SOL liquidity is below the configured minimum.")]
+    InsufficientMarketLiquidity,
+
+    /// 市场未开始错误
+    /// 当在开始槽位之前交易时触发
+    #[msg("The market has not started yet.")]
+    MarketNotStarted,
+
+    /// 结算时间未到错误
+    /// 当在可结算时间之前结算市场时触发
+    #[msg("The market is not yet eligible for resolution.")]
+    ResolutionTooEarly,
+
+    /// 保证金已结算错误
+    /// 当重复结算创建者保证金时触发
+    #[msg("The creator bond has already been settled.")]
+    BondAlreadySettled,
+
+    /// 手续费收款方错误
+    /// 当传入的收款方账户与配置不一致时触发
+    #[msg("Fee recipient accounts do not match the config.")]
+    InvalidFeeRecipient,
+
+    /// 暂停错误
+    /// 当平台或市场处于暂停状态时触发
+    #[msg("Trading is paused.")]
+    Paused,
+
+    /// 时间锁未到期错误
+    /// 当待生效配置尚未达到可执行slot时触发
+    #[msg("The config timelock has not expired yet.")]
+    ConfigTimelockActive,
+
+    /// 迁移金额不符错误
+    /// 当外部AMM未恰好取走迁移的SOL与代币时触发
+    #[msg("The AMM did not take exactly the migrated amounts.")]
+    MigrationAmountMismatch,
+
+    /// 手续费过高错误
+    /// 当平台费、LP费与创建者费之和超过100%时触发
+    #[msg("Total trading fees exceed 100%.")]
+    FeeTooHigh,
+
+    /// 市场ID错误
+    /// 当市场ID不等于 sha256(question ‖ rules_hash) 时触发
+    #[msg("Market id must be the hash of the question and rules hash.")]
+    InvalidMarketId,
+
+    /// 非获胜代币错误
+    /// 当市场有获胜方时尝试赎回失败一侧的代币时触发
+    #[msg("Only the winning outcome token can be redeemed.")]
+    NotWinningToken,
 }
@@ -3,6 +3,7 @@
 //! 定义预测市场合约中发出的各种事件
 //! 事件用于记录重要的状态变化和操作，便于前端监听和索引
 
+use crate::state::config::ConfigChange;
 use anchor_lang::prelude::*;
 
 /// 全局更新事件
@@ -36,6 +37,9 @@ pub struct CreateEvent {
     /// 市场账户地址
     pub market: Pubkey,
 
+    /// 市场ID
+    pub market_id: [u8; 32],
+
     /// YES代币地址
     pub token_yes: Pubkey,
     
@@ -65,6 +69,15 @@ pub struct CreateEvent {
     
     /// 结束槽位
     pub ending_slot: u64,
+
+    /// 开始交易时间（Unix时间戳）
+    pub start_timestamp: i64,
+
+    /// 结束交易时间（Unix时间戳）
+    pub ending_timestamp: i64,
+
+    /// 可结算时间（Unix时间戳）
+    pub resolution_timestamp: i64,
 }
 
 /// 提取事件
@@ -92,6 +105,27 @@ pub struct WithdrawEvent {
     pub withdraw_time: i64,
 }
 
+/// 创建者保证金结算事件
+/// 
+/// 市场结算后返还或罚没创建者保证金时发出
+#[event]
+pub struct BondSettledEvent {
+    /// 市场账户地址
+    pub market: Pubkey,
+
+    /// 市场创建者
+    pub creator: Pubkey,
+
+    /// 保证金接收方（返还时为创建者，罚没时为手续费金库）
+    pub recipient: Pubkey,
+
+    /// 保证金数量
+    pub amount: u64,
+
+    /// 是否被罚没
+    pub slashed: bool,
+}
+
 /// 交易事件
 /// 
 /// 当用户进行代币交易时发出
@@ -116,8 +150,11 @@ pub struct TradeEvent {
     /// 代币交易数量
     pub token_amount: u64,
     
-    /// 手续费（lamports）
+    /// 手续费（lamports，含平台费、LP费与创建者费）
     pub fee_lamports: u64,
+
+    /// 其中归市场创建者的手续费（lamports）
+    pub creator_fee_lamports: u64,
     
     /// 是否为买入操作
     pub is_buy: bool,
@@ -166,6 +203,51 @@ pub struct CompleteEvent {
     pub timestamp: i64,
 }
 
+/// 配置提案事件
+/// 
+/// 提交待生效配置时发出，包含相对当前配置的逐字段变更
+#[event]
+pub struct ConfigProposedEvent {
+    /// 提案发起者
+    pub proposer: Pubkey,
+
+    /// 最早可执行的slot
+    pub eta_slot: u64,
+
+    /// 变更的字段及新旧值
+    pub changes: Vec<ConfigChange>,
+}
+
+/// 配置生效事件
+/// 
+/// 时间锁到期、待生效配置写入全局配置时发出
+#[event]
+pub struct ConfigExecutedEvent {
+    /// 执行者
+    pub executor: Pubkey,
+
+    /// 提案发起者
+    pub proposer: Pubkey,
+
+    /// 变更的字段及新旧值
+    pub changes: Vec<ConfigChange>,
+}
+
+/// 配置提案取消事件
+/// 
+/// 待生效配置被撤销时发出，包含被撤销的逐字段变更
+#[event]
+pub struct ConfigCancelledEvent {
+    /// 取消者
+    pub canceller: Pubkey,
+
+    /// 提案发起者
+    pub proposer: Pubkey,
+
+    /// 被撤销的字段变更
+    pub changes: Vec<ConfigChange>,
+}
+
 /// 事件转换特征
 /// 
 /// 提供将结构体转换为事件的通用接口
@@ -0,0 +1,36 @@
+//! 管理员指令：接受角色（两步交接的第二步）
+
+use constants::CONFIG;
+use errors::PredictionMarketError;
+
+use crate::*;
+
+/// 账户集合：被提名者与全局配置
+#[derive(Accounts)]
+#[instruction(role: Role)]
+pub struct AcceptRole<'info> {
+    /// 被提名者（必须与该角色的 pending 字段一致）
+    #[account(
+        constraint = global_config.role(role).1 == new_holder.key() @PredictionMarketError::IncorrectAuthority
+    )]
+    pub new_holder: Signer<'info>,
+
+    /// 全局配置（存储角色当前/待定持有者）
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    global_config: Box<Account<'info, Config>>,
+}
+
+impl AcceptRole<'_> {
+    /// 将待定持有者提升为正式持有者，并清空 pending 字段
+    pub fn process(&mut self, role: Role) -> Result<()> {
+        let new_holder = self.new_holder.key();
+        let (holder, pending) = self.global_config.role_mut(role);
+        *holder = new_holder;
+        *pending = Pubkey::default();
+        Ok(())
+    }
+}
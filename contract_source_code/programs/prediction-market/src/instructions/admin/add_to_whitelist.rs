@@ -0,0 +1,47 @@
+//! 管理员指令：将创建者加入白名单
+
+use constants::CONFIG;
+use errors::PredictionMarketError;
+use state::whitelist::Whitelist;
+
+use crate::*;
+
+/// 账户集合：由白名单管理员为指定创建者创建白名单PDA
+#[derive(Accounts)]
+#[instruction(creator: Pubkey)]
+pub struct AddToWhitelist<'info> {
+    /// 白名单管理员（必须与全局配置中的 whitelist_manager 匹配）
+    #[account(
+        mut,
+        constraint = global_config.whitelist_manager == *admin.key @PredictionMarketError::IncorrectAuthority
+    )]
+    pub admin: Signer<'info>,
+
+    /// 全局配置
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    global_config: Box<Account<'info, Config>>,
+
+    /// 白名单账户（以创建者地址派生，存在即表示已加入白名单）
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Whitelist::INIT_SPACE,
+        seeds = [Whitelist::SEED_PREFIX.as_bytes(), creator.as_ref()],
+        bump
+    )]
+    pub whitelist: Box<Account<'info, Whitelist>>,
+
+    /// 系统程序
+    pub system_program: Program<'info, System>,
+}
+
+impl AddToWhitelist<'_> {
+    /// 记录白名单创建者地址
+    pub fn process(&mut self, creator: Pubkey) -> Result<()> {
+        self.whitelist.creator = creator;
+        Ok(())
+    }
+}
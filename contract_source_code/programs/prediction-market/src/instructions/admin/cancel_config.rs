@@ -0,0 +1,56 @@
+//! 管理员指令：取消待生效配置
+
+use constants::CONFIG;
+use errors::PredictionMarketError;
+use events::ConfigCancelledEvent;
+use state::pending_config::PendingConfig;
+
+use crate::*;
+
+/// 账户集合：由管理员或提案发起者取消配置提案
+#[derive(Accounts)]
+pub struct CancelConfig<'info> {
+    /// 管理员或提案发起者
+    #[account(
+        constraint = global_config.authority == signer.key()
+            || pending_config.proposer == signer.key() @PredictionMarketError::IncorrectAuthority
+    )]
+    pub signer: Signer<'info>,
+
+    /// 全局配置
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    global_config: Box<Account<'info, Config>>,
+
+    /// 待生效配置（取消后关闭，租金退还提案发起者）
+    #[account(
+        mut,
+        seeds = [PendingConfig::SEED_PREFIX.as_bytes()],
+        bump = pending_config.bump,
+        close = proposer,
+    )]
+    pub pending_config: Box<Account<'info, PendingConfig>>,
+
+    /// 提案发起者（接收待生效配置账户的租金）
+    /// CHECK: should be same with the proposer in the pending_config
+    #[account(
+        mut,
+        constraint = pending_config.proposer == proposer.key() @PredictionMarketError::IncorrectAuthority
+    )]
+    pub proposer: AccountInfo<'info>,
+}
+
+impl CancelConfig<'_> {
+    /// 撤销待生效配置
+    pub fn process(&mut self) -> Result<()> {
+        emit!(ConfigCancelledEvent {
+            canceller: self.signer.key(),
+            proposer: self.pending_config.proposer,
+            changes: self.global_config.diff(&self.pending_config.config),
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+//! 平台指令：按权重分配手续费金库中的平台手续费（任何人均可触发）
+
+use crate::{
+    constants::CONFIG,
+    errors::PredictionMarketError,
+    state::{config::*, fee_vault::FeeVault},
+};
+use anchor_lang::prelude::*;
+
+/// 账户集合：分配手续费所需账户
+///
+/// 各收款方账户通过 remaining_accounts 按 `Config.fee_recipients` 的顺序传入（需可写）
+#[derive(Accounts)]
+pub struct DistributeFees<'info> {
+    /// 全局配置
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    global_config: Box<Account<'info, Config>>,
+
+    /// 手续费金库
+    #[account(
+        mut,
+        seeds = [FeeVault::SEED_PREFIX.as_bytes()],
+        bump = fee_vault.bump,
+    )]
+    pub fee_vault: Box<Account<'info, FeeVault>>,
+}
+
+impl<'info> DistributeFees<'info> {
+    /// 将金库中可分配的手续费按权重分配，整除余数留在金库中待下次分配
+    ///
+    /// 团队钱包对应的份额不直接转出，而是划归金库管理员（记入 `treasury_fees`），由 withdraw_fees 提取；
+    /// 未配置收款方时全部手续费划归金库管理员
+    pub fn handler(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        let recipients = &self.global_config.fee_recipients;
+        require!(
+            remaining_accounts.len() == recipients.len(),
+            PredictionMarketError::InvalidFeeRecipient
+        );
+
+        let total_weight: u64 = recipients.iter().map(|r| r.weight as u64).sum();
+        let fee_vault_info = self.fee_vault.to_account_info();
+        let available = self.fee_vault.distributable_fees(&fee_vault_info)?;
+        require!(available > 0, PredictionMarketError::InvalidAmount);
+
+        let mut distributed: u64 = 0;
+        let mut earmarked: u64 = if recipients.is_empty() { available } else { 0 };
+        for (recipient, account) in recipients.iter().zip(remaining_accounts) {
+            require!(
+                account.key() == recipient.wallet && account.is_writable,
+                PredictionMarketError::InvalidFeeRecipient
+            );
+
+            let share: u64 = (available as u128)
+                .checked_mul(recipient.weight as u128)
+                .and_then(|v| v.checked_div(total_weight as u128))
+                .and_then(|v| v.try_into().ok())
+                .ok_or(PredictionMarketError::ArithmeticError)?;
+            if share == 0 {
+                continue;
+            }
+
+            if recipient.wallet == self.global_config.team_wallet {
+                earmarked = earmarked
+                    .checked_add(share)
+                    .ok_or(PredictionMarketError::OverflowOrUnderflowOccurred)?;
+                continue;
+            }
+
+            FeeVault::pay_out(&fee_vault_info, account, share)?;
+            distributed = distributed
+                .checked_add(share)
+                .ok_or(PredictionMarketError::OverflowOrUnderflowOccurred)?;
+        }
+
+        self.fee_vault.treasury_fees = self
+            .fee_vault
+            .treasury_fees
+            .checked_add(earmarked)
+            .ok_or(PredictionMarketError::OverflowOrUnderflowOccurred)?;
+        self.fee_vault.total_distributed = self
+            .fee_vault
+            .total_distributed
+            .checked_add(distributed)
+            .ok_or(PredictionMarketError::OverflowOrUnderflowOccurred)?;
+
+        msg!("DistributeFees: distributed={}, earmarked={}", distributed, earmarked);
+
+        Ok(())
+    }
+}
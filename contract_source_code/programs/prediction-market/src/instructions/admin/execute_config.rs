@@ -0,0 +1,78 @@
+//! 管理员指令：时间锁到期后执行待生效配置
+
+use constants::CONFIG;
+use errors::PredictionMarketError;
+use events::ConfigExecutedEvent;
+use state::pending_config::PendingConfig;
+
+use crate::*;
+
+/// 账户集合：任何人均可在时间锁到期后执行配置提案
+#[derive(Accounts)]
+pub struct ExecuteConfig<'info> {
+    /// 执行者（支付配置账户扩容所需的租金）
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// 待生效配置（执行后关闭，租金退还提案发起者）
+    #[account(
+        mut,
+        seeds = [PendingConfig::SEED_PREFIX.as_bytes()],
+        bump = pending_config.bump,
+        close = proposer,
+    )]
+    pub pending_config: Box<Account<'info, PendingConfig>>,
+
+    /// 全局配置（按新配置大小调整账户空间）
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump,
+        realloc = pending_config.config.space(),
+        realloc::payer = payer,
+        realloc::zero = false,
+    )]
+    global_config: Box<Account<'info, Config>>,
+
+    /// 提案发起者（接收待生效配置账户的租金）
+    /// CHECK: should be same with the proposer in the pending_config
+    #[account(
+        mut,
+        constraint = pending_config.proposer == proposer.key() @PredictionMarketError::IncorrectAuthority
+    )]
+    pub proposer: AccountInfo<'info>,
+
+    /// 系统程序
+    system_program: Program<'info, System>,
+}
+
+impl ExecuteConfig<'_> {
+    /// 将待生效配置写入全局配置
+    pub fn process(&mut self) -> Result<()> {
+        require!(
+            Clock::get()?.slot >= self.pending_config.eta_slot,
+            PredictionMarketError::ConfigTimelockActive
+        );
+
+        // 提案期间角色可能已交接，按当前配置保留受管字段并重新校验提案发起者权限
+        let mut new_config = self.pending_config.config.clone();
+        new_config.keep_managed_fields_from(&self.global_config);
+
+        let proposer = self.pending_config.proposer;
+        require!(
+            new_config.can_be_proposed_by(&self.global_config, proposer),
+            PredictionMarketError::IncorrectAuthority
+        );
+
+        let changes = self.global_config.diff(&new_config);
+        self.global_config.set_inner(new_config);
+
+        emit!(ConfigExecutedEvent {
+            executor: self.payer.key(),
+            proposer,
+            changes,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,37 @@
+//! 管理员指令：为已部署的程序补建手续费金库（configure 只在首次配置时创建）
+
+use state::fee_vault::FeeVault;
+
+use crate::*;
+
+/// 账户集合：创建手续费金库PDA
+///
+/// 金库不含可配置数据，任何人均可代付租金创建，且只能创建一次
+#[derive(Accounts)]
+pub struct InitFeeVault<'info> {
+    /// 付费者
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// 手续费金库（PDA，归集平台手续费）
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + FeeVault::INIT_SPACE,
+        seeds = [FeeVault::SEED_PREFIX.as_bytes()],
+        bump,
+    )]
+    pub fee_vault: Box<Account<'info, FeeVault>>,
+
+    /// 系统程序
+    pub system_program: Program<'info, System>,
+}
+
+impl InitFeeVault<'_> {
+    /// 写入金库bump
+    pub fn process(&mut self, fee_vault_bump: u8) -> Result<()> {
+        self.fee_vault.bump = fee_vault_bump;
+        msg!("InitFeeVault: fee_vault={}", self.fee_vault.key());
+        Ok(())
+    }
+}
@@ -0,0 +1,100 @@
+//! 管理员指令：将已部署的旧版全局配置升级为当前布局（一次性）
+
+use anchor_lang::system_program;
+use constants::CONFIG;
+use errors::PredictionMarketError;
+
+use crate::*;
+
+/// 账户集合：由旧配置中的管理员升级配置账户
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    /// 管理员（必须与旧配置中的 authority 匹配，支付扩容所需的租金）
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// 全局配置（旧布局无法按 `Config` 反序列化，在指令内部读取、扩容并重写）
+    /// CHECK: legacy layout is checked and rewritten inside the instruction
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump,
+        owner = crate::ID,
+    )]
+    config: AccountInfo<'info>,
+
+    /// 系统程序
+    system_program: Program<'info, System>,
+}
+
+impl MigrateConfig<'_> {
+    /// 读取旧配置，沿用其已有字段，新增字段取自 `new_config`，扩容后按当前布局写回
+    pub fn process(&mut self, mut new_config: Config) -> Result<()> {
+        // 只接受旧版大小的账户：升级后的账户更大，因此本指令只能成功执行一次
+        let legacy = {
+            let data = self.config.try_borrow_data()?;
+            require!(
+                data.len() == LegacyConfig::SPACE && data[..8] == *Config::DISCRIMINATOR,
+                PredictionMarketError::IncorrectConfigAccount
+            );
+            LegacyConfig::deserialize(&mut &data[8..])?
+        };
+        require!(
+            legacy.authority == self.admin.key(),
+            PredictionMarketError::IncorrectAuthority
+        );
+
+        // 沿用旧配置中已有的字段
+        new_config.authority = legacy.authority;
+        new_config.pending_authority = legacy.pending_authority;
+        new_config.team_wallet = legacy.team_wallet;
+        new_config.platform_buy_fee = legacy.platform_buy_fee;
+        new_config.platform_sell_fee = legacy.platform_sell_fee;
+        new_config.lp_buy_fee = legacy.lp_buy_fee;
+        new_config.lp_sell_fee = legacy.lp_sell_fee;
+        new_config.token_supply_config = legacy.token_supply_config;
+        new_config.token_decimals_config = legacy.token_decimals_config;
+        new_config.initial_real_token_reserves_config = legacy.initial_real_token_reserves_config;
+        new_config.min_sol_liquidity = legacy.min_sol_liquidity;
+        new_config.initialized = legacy.initialized;
+        new_config.paused = false;
+
+        // 未指定的角色默认由管理员持有
+        for role in Role::ALL {
+            let (holder, pending) = new_config.role_mut(role);
+            if *holder == Pubkey::default() {
+                *holder = legacy.authority;
+            }
+            *pending = Pubkey::default();
+        }
+        new_config.validate()?;
+
+        // 补足租金并扩容
+        let serialized_config =
+            [Config::DISCRIMINATOR, new_config.try_to_vec()?.as_slice()].concat();
+        let serialized_config_len = serialized_config.len();
+        let config_cost = Rent::get()?.minimum_balance(serialized_config_len);
+        let lamport_delta = config_cost.saturating_sub(self.config.lamports());
+        if lamport_delta > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: self.admin.to_account_info(),
+                        to: self.config.to_account_info(),
+                    },
+                ),
+                lamport_delta,
+            )?;
+        }
+        self.config.resize(serialized_config_len)?;
+
+        // 写入序列化配置
+        self.config
+            .try_borrow_mut_data()?
+            .copy_from_slice(serialized_config.as_slice());
+
+        msg!("MigrateConfig: authority={}", legacy.authority);
+        Ok(())
+    }
+}
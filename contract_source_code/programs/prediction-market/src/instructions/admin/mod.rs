@@ -1,3 +1,16 @@
 pub mod accept_authority;
+pub mod accept_role;
+pub mod add_to_whitelist;
+pub mod cancel_config;
 pub mod configure;
+pub mod distribute_fees;
+pub mod execute_config;
+pub mod init_fee_vault;
+pub mod migrate_config;
 pub mod nominate_authority;
+pub mod nominate_role;
+pub mod propose_config;
+pub mod remove_from_whitelist;
+pub mod set_global_pause;
+pub mod set_market_pause;
+pub mod withdraw_fees;
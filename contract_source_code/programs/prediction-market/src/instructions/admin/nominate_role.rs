@@ -0,0 +1,35 @@
+//! 管理员指令：提名角色的新持有者（两步交接的第一步）
+
+use constants::CONFIG;
+use errors::PredictionMarketError;
+
+use crate::*;
+
+/// 账户集合：由管理员或该角色的当前持有者发起提名
+#[derive(Accounts)]
+#[instruction(role: Role)]
+pub struct NominateRole<'info> {
+    /// 管理员或该角色的当前持有者
+    #[account(
+        constraint = global_config.authority == signer.key()
+            || global_config.role(role).0 == signer.key() @PredictionMarketError::IncorrectAuthority
+    )]
+    pub signer: Signer<'info>,
+
+    /// 全局配置（写入对应角色的 pending 字段）
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    global_config: Box<Account<'info, Config>>,
+}
+
+impl NominateRole<'_> {
+    /// 将新持有者写入该角色的 pending 字段，等待其调用 accept_role 接受
+    pub fn process(&mut self, role: Role, new_holder: Pubkey) -> Result<()> {
+        let (_, pending) = self.global_config.role_mut(role);
+        *pending = new_holder;
+        Ok(())
+    }
+}
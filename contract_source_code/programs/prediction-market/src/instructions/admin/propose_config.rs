@@ -0,0 +1,79 @@
+//! 管理员指令：提交配置变更提案（时间锁到期后由 execute_config 生效）
+
+use constants::CONFIG;
+use errors::PredictionMarketError;
+use events::ConfigProposedEvent;
+use state::pending_config::PendingConfig;
+
+use crate::*;
+
+/// 账户集合：由管理员、手续费管理员或金库管理员提交待生效配置
+#[derive(Accounts)]
+#[instruction(new_config: Config)]
+pub struct ProposeConfig<'info> {
+    /// 管理员、手续费管理员或金库管理员（后两者只能提交各自职责内的变更）
+    #[account(
+        mut,
+        constraint = global_config.authority == *admin.key
+            || global_config.fee_admin == *admin.key
+            || global_config.treasury == *admin.key @PredictionMarketError::IncorrectAuthority
+    )]
+    pub admin: Signer<'info>,
+
+    /// 全局配置
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    global_config: Box<Account<'info, Config>>,
+
+    /// 待生效配置（同一时间只能存在一个提案）
+    #[account(
+        init,
+        payer = admin,
+        space = PendingConfig::space(&new_config),
+        seeds = [PendingConfig::SEED_PREFIX.as_bytes()],
+        bump,
+    )]
+    pub pending_config: Box<Account<'info, PendingConfig>>,
+
+    /// 系统程序
+    system_program: Program<'info, System>,
+}
+
+impl ProposeConfig<'_> {
+    /// 校验并记录待生效配置，最早可在 当前slot + 时间锁 后执行
+    pub fn process(&mut self, mut new_config: Config, pending_config_bump: u8) -> Result<()> {
+        new_config.validate()?;
+
+        // 管理员与角色、暂停状态只能通过各自的指令变更
+        new_config.keep_managed_fields_from(&self.global_config);
+
+        // 管理员可修改全部配置；手续费管理员只能修改费率，金库管理员只能修改手续费收款方
+        require!(
+            new_config.can_be_proposed_by(&self.global_config, self.admin.key()),
+            PredictionMarketError::IncorrectAuthority
+        );
+
+        let eta_slot = Clock::get()?
+            .slot
+            .checked_add(self.global_config.config_timelock_slots)
+            .ok_or(PredictionMarketError::ArithmeticError)?;
+
+        let changes = self.global_config.diff(&new_config);
+
+        let pending_config = &mut self.pending_config;
+        pending_config.proposer = self.admin.key();
+        pending_config.eta_slot = eta_slot;
+        pending_config.bump = pending_config_bump;
+        pending_config.config = new_config;
+
+        emit!(ConfigProposedEvent {
+            proposer: self.admin.key(),
+            eta_slot,
+            changes,
+        });
+
+        Ok(())
+    }
+}
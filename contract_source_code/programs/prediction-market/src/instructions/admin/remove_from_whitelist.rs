@@ -0,0 +1,42 @@
+//! 管理员指令：将创建者移出白名单
+
+use constants::CONFIG;
+use errors::PredictionMarketError;
+use state::whitelist::Whitelist;
+
+use crate::*;
+
+/// 账户集合：由白名单管理员关闭指定创建者的白名单PDA（租金退还管理员）
+#[derive(Accounts)]
+#[instruction(creator: Pubkey)]
+pub struct RemoveFromWhitelist<'info> {
+    /// 白名单管理员（必须与全局配置中的 whitelist_manager 匹配）
+    #[account(
+        mut,
+        constraint = global_config.whitelist_manager == *admin.key @PredictionMarketError::IncorrectAuthority
+    )]
+    pub admin: Signer<'info>,
+
+    /// 全局配置
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    global_config: Box<Account<'info, Config>>,
+
+    /// 白名单账户（关闭后该创建者不再处于白名单）
+    #[account(
+        mut,
+        close = admin,
+        seeds = [Whitelist::SEED_PREFIX.as_bytes(), creator.as_ref()],
+        bump
+    )]
+    pub whitelist: Box<Account<'info, Whitelist>>,
+}
+
+impl RemoveFromWhitelist<'_> {
+    /// 关闭白名单账户（由 `close` 约束完成）
+    pub fn process(&mut self, _creator: Pubkey) -> Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,33 @@
+//! 管理员指令：切换全局暂停开关
+
+use constants::CONFIG;
+use errors::PredictionMarketError;
+
+use crate::*;
+
+/// 账户集合：由暂停员切换全局暂停状态
+#[derive(Accounts)]
+pub struct SetGlobalPause<'info> {
+    /// 暂停员（必须与全局配置中的 pauser 匹配）
+    #[account(
+        constraint = global_config.pauser == *admin.key @PredictionMarketError::IncorrectAuthority
+    )]
+    pub admin: Signer<'info>,
+
+    /// 全局配置（写入 paused）
+    #[account(
+        mut,
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    global_config: Box<Account<'info, Config>>,
+}
+
+impl SetGlobalPause<'_> {
+    /// 写入全局暂停状态
+    pub fn process(&mut self, paused: bool) -> Result<()> {
+        self.global_config.paused = paused;
+        msg!("SetGlobalPause: paused={}", paused);
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+//! 管理员指令：切换单个市场的暂停开关
+
+use anchor_spl::token::Mint;
+use constants::{CONFIG, MARKET};
+use errors::PredictionMarketError;
+
+use crate::*;
+
+/// 账户集合：由暂停员切换指定市场的暂停状态
+#[derive(Accounts)]
+pub struct SetMarketPause<'info> {
+    /// 暂停员（必须与全局配置中的 pauser 匹配）
+    #[account(
+        constraint = global_config.pauser == *admin.key @PredictionMarketError::IncorrectAuthority
+    )]
+    pub admin: Signer<'info>,
+
+    /// 全局配置
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    global_config: Box<Account<'info, Config>>,
+
+    /// 市场账户（写入 paused）
+    #[account(
+        mut,
+        seeds = [MARKET.as_bytes(), &yes_token.key().to_bytes(), &no_token.key().to_bytes()],
+        bump
+    )]
+    market: Box<Account<'info, Market>>,
+
+    /// YES/NO 代币mint
+    pub yes_token: Box<Account<'info, Mint>>,
+    pub no_token: Box<Account<'info, Mint>>,
+}
+
+impl SetMarketPause<'_> {
+    /// 写入市场暂停状态
+    pub fn process(&mut self, paused: bool) -> Result<()> {
+        self.market.paused = paused;
+        msg!("SetMarketPause: market={}, paused={}", self.market.key(), paused);
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+//! 金库管理员指令：从手续费金库提取平台手续费至团队钱包
+
+use crate::{
+    constants::CONFIG,
+    errors::PredictionMarketError,
+    events::WithdrawEvent,
+    state::{config::*, fee_vault::FeeVault},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token::native_mint;
+
+/// 账户集合：提取手续费所需账户
+#[derive(Accounts)]
+pub struct WithdrawFees<'info> {
+    /// 金库管理员（必须与全局配置中的 treasury 匹配）
+    #[account(
+        constraint = global_config.treasury == admin.key() @PredictionMarketError::IncorrectAuthority
+    )]
+    pub admin: Signer<'info>,
+
+    /// 全局配置
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    global_config: Box<Account<'info, Config>>,
+
+    /// 手续费金库
+    #[account(
+        mut,
+        seeds = [FeeVault::SEED_PREFIX.as_bytes()],
+        bump = fee_vault.bump,
+    )]
+    pub fee_vault: Box<Account<'info, FeeVault>>,
+
+    /// 团队钱包（需与配置一致）
+    /// CHECK: should be same with the address in the global_config
+    #[account(
+        mut,
+        constraint = global_config.team_wallet == team_wallet.key() @PredictionMarketError::IncorrectTeamWallet
+    )]
+    pub team_wallet: AccountInfo<'info>,
+}
+
+impl WithdrawFees<'_> {
+    /// 从金库管理员已划归的份额中提取指定数量的手续费并累加链上提取总额
+    ///
+    /// 其余收款方的份额只能经 distribute_fees 按权重分配，金库管理员无法越过分配比例提取
+    pub fn handler(&mut self, amount: u64) -> Result<()> {
+        require!(amount > 0, PredictionMarketError::InvalidAmount);
+
+        let fee_vault_info = self.fee_vault.to_account_info();
+        require!(
+            amount <= self.fee_vault.treasury_fees
+                && amount <= FeeVault::available_fees(&fee_vault_info)?,
+            PredictionMarketError::InsufficientSol
+        );
+
+        FeeVault::pay_out(&fee_vault_info, &self.team_wallet, amount)?;
+
+        self.fee_vault.treasury_fees -= amount;
+
+        self.fee_vault.total_withdrawn = self
+            .fee_vault
+            .total_withdrawn
+            .checked_add(amount)
+            .ok_or(PredictionMarketError::OverflowOrUnderflowOccurred)?;
+
+        emit!(WithdrawEvent {
+            withdraw_authority: self.admin.key(),
+            mint: native_mint::ID,
+            fee_vault: self.fee_vault.key(),
+            withdrawn: amount,
+            total_withdrawn: self.fee_vault.total_withdrawn,
+            withdraw_time: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -1,14 +1,15 @@
 //! 市场指令：添加流动性（LP）
 
 use crate::{
-    constants::{CONFIG, GLOBAL, MARKET, USERINFO},
+    constants::{CONFIG, GLOBAL, LP_MINT, LP_MINT_DECIMALS, MARKET, USERINFO},
     errors::PredictionMarketError,
-    state::{config::*, market::*},
+    state::{config::*, lp_position::LpPosition, market::*},
+    utils::token_mint_with_signer,
 };
 use anchor_lang::{prelude::*, system_program};
 use anchor_spl::{
     associated_token::{self, AssociatedToken},
-    token::{self, Mint, Token},
+    token::{self, Mint, Token, TokenAccount},
 };
 
 /// 账户集合：添加LP所需账户
@@ -22,14 +23,6 @@ pub struct AddLiquidity<'info> {
     )]
     global_config: Box<Account<'info, Config>>,
 
-    /// 团队钱包（需与配置一致，用于收取平台费）
-    /// CHECK: should be same with the address in the global_config
-    #[account(
-        mut,
-        constraint = global_config.team_wallet == team_wallet.key() @PredictionMarketError::IncorrectAuthority
-    )]
-    pub team_wallet: AccountInfo<'info>,
-
     /// 市场账户（以YES/NO mint作为种子）
     #[account(
         mut,
@@ -51,6 +44,26 @@ pub struct AddLiquidity<'info> {
     pub yes_token: Box<Account<'info, Mint>>,
     pub no_token: Box<Account<'info, Mint>>,
 
+    /// 市场LP份额mint（PDA，由全局金库作为mint authority，按需创建）
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [LP_MINT.as_bytes(), &market.key().to_bytes()],
+        bump,
+        mint::decimals = LP_MINT_DECIMALS,
+        mint::authority = global_vault,
+    )]
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    /// LP份额托管ATA（归全局金库所有，存放各仓位名下的LP份额，按需创建）
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = lp_mint,
+        associated_token::authority = global_vault,
+    )]
+    pub lp_custody: Box<Account<'info, TokenAccount>>,
+
     /// 用户信息（按需初始化）
     #[account(
         init_if_needed,
@@ -61,6 +74,16 @@ pub struct AddLiquidity<'info> {
     )]
     pub user_info: Box<Account<'info, UserInfo>>,
 
+    /// LP仓位（按市场与LP派生，按需初始化）
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + LpPosition::INIT_SPACE,
+        seeds = [LpPosition::SEED_PREFIX.as_bytes(), &market.key().to_bytes(), &user.key().to_bytes()],
+        bump
+    )]
+    pub lp_position: Box<Account<'info, LpPosition>>,
+
     /// 用户签名者
     #[account(mut)]
     pub user: Signer<'info>,
@@ -75,17 +98,79 @@ pub struct AddLiquidity<'info> {
 }
 
 impl<'info> AddLiquidity<'info> { 
-    /// 处理添加LP；当前为占位实现，预留LP逻辑
-    pub fn handler(&mut self, amount: u64) -> Result<()> {
+    /// 处理添加LP：注入SOL并同比例扩充两侧储备，随后按比例铸造LP份额代币到托管ATA并记入LP仓位
+    pub fn handler(&mut self, amount: u64, global_vault_bump: u8, lp_position_bump: u8) -> Result<()> {
         require!(self.market.is_completed == false, PredictionMarketError::MarketIsCompleted);
-        require!(amount > 0, PredictionMarketError::WITHDRAWLIQUIDITYSOLAMOUNTERROR);
+        require!(
+            !self.global_config.paused && !self.market.paused,
+            PredictionMarketError::Paused
+        );
+        require!(amount > 0, PredictionMarketError::InvalidAmount);
+
+        // 市场结束后不再接受注入
+        let clock = Clock::get()?;
+        if let Some(ending_slot) = self.market.ending_slot {
+            require!(clock.slot < ending_slot, PredictionMarketError::InvalidEndTime);
+        }
+        if let Some(ending_timestamp) = self.market.ending_timestamp {
+            require!(
+                clock.unix_timestamp < ending_timestamp,
+                PredictionMarketError::InvalidEndTime
+            );
+        }
+
+        // 按注入前的池子状态计算份额，避免本次注入稀释自身
+        let shares = self
+            .market
+            .lp_shares_for_amount(amount)
+            .ok_or(PredictionMarketError::ArithmeticError)?;
+        require!(shares > 0, PredictionMarketError::InvalidAmount);
 
         self.market.add_liquidity(
             &mut self.global_vault.to_account_info(),
             &self.user,
             amount,
-            &mut self.user_info,
             &self.system_program,
-        )
+        )?;
+
+        if !self.user_info.is_initialized {
+            self.user_info.user = self.user.key();
+            self.user_info.is_initialized = true;
+        }
+        self.user_info.is_lp = true;
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            GLOBAL.as_bytes(),
+            &[global_vault_bump],
+        ]];
+
+        self.lp_position
+            .init_if_empty(self.market.key(), self.user.key(), lp_position_bump);
+
+        token_mint_with_signer(
+            self.lp_mint.to_account_info(),
+            self.lp_custody.to_account_info(),
+            self.global_vault.to_account_info(),
+            &self.token_program,
+            signer_seeds,
+            shares,
+        )?;
+
+        self.market.total_lp_shares = self
+            .market
+            .total_lp_shares
+            .checked_add(shares)
+            .ok_or(PredictionMarketError::OverflowOrUnderflowOccurred)?;
+        // 份额变化前先结算已累计的LP手续费
+        self.market
+            .credit_lp_position(&mut self.lp_position, shares)
+            .ok_or(PredictionMarketError::ArithmeticError)?;
+        self.lp_position.deposit_amount = self
+            .lp_position
+            .deposit_amount
+            .checked_add(amount)
+            .ok_or(PredictionMarketError::OverflowOrUnderflowOccurred)?;
+
+        Ok(())
     }
 }
\ No newline at end of file
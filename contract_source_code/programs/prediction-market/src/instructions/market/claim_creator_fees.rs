@@ -0,0 +1,77 @@
+//! 市场指令：领取市场创建者手续费
+
+use crate::{
+    constants::{GLOBAL, MARKET},
+    errors::PredictionMarketError,
+    state::market::*,
+    utils::sol_transfer_with_signer,
+};
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::token::Mint;
+
+/// 账户集合：领取创建者手续费所需账户
+#[derive(Accounts)]
+pub struct ClaimCreatorFees<'info> {
+    /// 市场账户
+    #[account(
+        mut,
+        seeds = [MARKET.as_bytes(), &yes_token.key().to_bytes(), &no_token.key().to_bytes()],
+        bump
+    )]
+    market: Account<'info, Market>,
+
+    /// 全局金库（PDA，存放SOL）
+    /// CHECK: global vault pda which stores SOL
+    #[account(
+        mut,
+        seeds = [GLOBAL.as_bytes()],
+        bump,
+    )]
+    pub global_vault: AccountInfo<'info>,
+
+    /// YES/NO 代币mint
+    pub yes_token: Box<Account<'info, Mint>>,
+    pub no_token: Box<Account<'info, Mint>>,
+
+    /// 市场创建者
+    #[account(
+        mut,
+        constraint = market.creator == creator.key() @PredictionMarketError::IncorrectAuthority
+    )]
+    pub creator: Signer<'info>,
+
+    /// 系统程序
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimCreatorFees<'info> {
+    /// 将累计的创建者手续费转给市场创建者
+    pub fn handler(&mut self, global_vault_bump: u8) -> Result<()> {
+        let amount = self.market.creator_fee_reserve;
+        require!(amount > 0, PredictionMarketError::InvalidAmount);
+
+        // 全局金库必须保持免租
+        let rent_exempt = Rent::get()?.minimum_balance(0);
+        require!(
+            self.global_vault.lamports().saturating_sub(amount) >= rent_exempt,
+            PredictionMarketError::InsufficientSol
+        );
+
+        let signer_seeds: &[&[&[u8]]] = &[&[GLOBAL.as_bytes(), &[global_vault_bump]]];
+
+        sol_transfer_with_signer(
+            self.global_vault.to_account_info(),
+            self.creator.to_account_info(),
+            &self.system_program,
+            signer_seeds,
+            amount,
+        )?;
+
+        self.market.creator_fee_reserve = 0;
+
+        msg!("ClaimCreatorFees: creator={}, amount={}", self.creator.key(), amount);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,87 @@
+//! 市场指令：领取LP手续费（不提取流动性）
+
+use crate::{
+    constants::{GLOBAL, MARKET},
+    errors::PredictionMarketError,
+    state::{lp_position::LpPosition, market::*},
+    utils::sol_transfer_with_signer,
+};
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::token::Mint;
+
+/// 账户集合：领取LP手续费所需账户
+#[derive(Accounts)]
+pub struct ClaimLpFees<'info> {
+    /// 市场账户
+    #[account(
+        mut,
+        seeds = [MARKET.as_bytes(), &yes_token.key().to_bytes(), &no_token.key().to_bytes()],
+        bump
+    )]
+    market: Account<'info, Market>,
+
+    /// 全局金库（PDA，存放SOL）
+    /// CHECK: global vault pda which stores SOL
+    #[account(
+        mut,
+        seeds = [GLOBAL.as_bytes()],
+        bump,
+    )]
+    pub global_vault: AccountInfo<'info>,
+
+    /// YES/NO 代币mint
+    pub yes_token: Box<Account<'info, Mint>>,
+    pub no_token: Box<Account<'info, Mint>>,
+
+    /// LP仓位
+    #[account(
+        mut,
+        seeds = [LpPosition::SEED_PREFIX.as_bytes(), &market.key().to_bytes(), &user.key().to_bytes()],
+        bump = lp_position.bump
+    )]
+    pub lp_position: Box<Account<'info, LpPosition>>,
+
+    /// 用户签名者
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// 系统程序
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimLpFees<'info> {
+    /// 结算并领取该LP累计的手续费
+    pub fn handler(&mut self, global_vault_bump: u8) -> Result<()> {
+        self.market
+            .settle_lp_fees(&mut self.lp_position)
+            .ok_or(PredictionMarketError::ArithmeticError)?;
+
+        let amount = self.lp_position.pending_fees;
+        require!(amount > 0, PredictionMarketError::InvalidAmount);
+
+        // 全局金库必须保持免租
+        let rent_exempt = Rent::get()?.minimum_balance(0);
+        require!(
+            self.global_vault.lamports().saturating_sub(amount) >= rent_exempt,
+            PredictionMarketError::InsufficientSol
+        );
+
+        let signer_seeds: &[&[&[u8]]] = &[&[GLOBAL.as_bytes(), &[global_vault_bump]]];
+
+        sol_transfer_with_signer(
+            self.global_vault.to_account_info(),
+            self.user.to_account_info(),
+            &self.system_program,
+            signer_seeds,
+            amount,
+        )?;
+
+        self.lp_position.pending_fees = 0;
+        self.market.lp_fee_reserve = self.market.lp_fee_reserve.saturating_sub(amount);
+
+        msg!("ClaimLpFees: user={}, amount={}", self.user.key(), amount);
+
+        Ok(())
+    }
+}
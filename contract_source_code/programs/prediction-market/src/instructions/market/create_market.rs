@@ -1,10 +1,14 @@
-//! 市场指令：创建市场（含YES mint、元数据、金库ATA等）
+//! 市场指令：原子创建市场（YES/NO mint、元数据、金库ATA与市场账户）
 
 use crate::{
-    constants::{CONFIG, GLOBAL, MARKET, METADATA, YES_NAME},
+    constants::{
+        CONFIG, CREATOR_BOND, FEE_BASIS_POINTS, GLOBAL, MARKET, MAX_START_SLOT_DELAY,
+        MAX_START_TIME_DELAY, METADATA, NO_MINT, NO_NAME, YES_MINT, YES_NAME,
+    },
     errors::*,
-    state::{config::*, market::*},
+    state::{config::*, market::*, market_metadata::MarketMetadata, whitelist::Whitelist},
     events::CreateEvent,
+    utils::sol_transfer_from_user,
 };
 use anchor_lang::{prelude::*, solana_program::sysvar::SysvarId, system_program};
 use anchor_spl::{
@@ -15,6 +19,7 @@ use anchor_spl::{
 
 /// 账户集合：创建市场所需账户
 #[derive(Accounts)]
+#[instruction(params: CreateMarketParams)]
 pub struct CreateMarket<'info> {
     /// 全局配置
     #[account(
@@ -37,28 +42,64 @@ pub struct CreateMarket<'info> {
     #[account(mut)]
     creator: Signer<'info>,
 
-    /// YES代币mint（由全局金库作为mint authority）
+    /// 创建者的白名单账户（配置开启白名单时必须提供）
+    #[account(
+        seeds = [Whitelist::SEED_PREFIX.as_bytes(), creator.key().as_ref()],
+        bump
+    )]
+    whitelist: Option<Box<Account<'info, Whitelist>>>,
+
+    /// YES代币mint（由市场ID派生的PDA，全局金库作为mint authority）
     #[account(
         init,
         payer = creator,
+        seeds = [YES_MINT.as_bytes(), params.market_id.as_ref()],
+        bump,
         mint::decimals = global_config.token_decimals_config,
         mint::authority = global_vault.key(),
     )]
     yes_token: Box<Account<'info, Mint>>,
 
-    /// NO代币mint（需在mint_no_token指令中创建）
-    pub no_token: Box<Account<'info, Mint>>,
+    /// NO代币mint（由市场ID派生的PDA，全局金库作为mint authority）
+    #[account(
+        init,
+        payer = creator,
+        seeds = [NO_MINT.as_bytes(), params.market_id.as_ref()],
+        bump,
+        mint::decimals = global_config.token_decimals_config,
+        mint::authority = global_vault.key(),
+    )]
+    no_token: Box<Account<'info, Mint>>,
 
     /// 市场账户（以YES/NO mint作为种子）
     #[account(
         init,
         payer = creator,
-        space = 8 + std::mem::size_of::<Market>(),
+        space = 8 + Market::INIT_SPACE,
         seeds = [MARKET.as_bytes(), &yes_token.key().to_bytes(), &no_token.key().to_bytes()],
         bump
     )]
     market: Box<Account<'info, Market>>,
 
+    /// 市场元数据（问题与结算规则，创建后不可修改）
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + MarketMetadata::INIT_SPACE,
+        seeds = [MarketMetadata::SEED_PREFIX.as_bytes(), market.key().as_ref()],
+        bump
+    )]
+    market_metadata: Box<Account<'info, MarketMetadata>>,
+
+    /// 创建者保证金托管（按市场派生的PDA，存放SOL）
+    /// CHECK: bond escrow pda which stores SOL
+    #[account(
+        mut,
+        seeds = [CREATOR_BOND.as_bytes(), market.key().as_ref()],
+        bump,
+    )]
+    pub bond_escrow: AccountInfo<'info>,
+
     /// YES元数据账户（传递给 Metadata 程序）
     /// CHECK: passed to token metadata program
     #[account(mut,
@@ -100,6 +141,20 @@ pub struct CreateMarket<'info> {
     )]
     global_yes_token_account: UncheckedAccount<'info>,
 
+    /// 全局金库的NO ATA（在指令中创建）
+    /// CHECK: created in instruction
+    #[account(
+        mut,
+        seeds = [
+            global_vault.key().as_ref(),
+            token::spl_token::ID.as_ref(),
+            no_token.key().as_ref(),
+        ],
+        bump,
+        seeds::program = associated_token::ID
+    )]
+    global_no_token_account: UncheckedAccount<'info>,
+
     /// 系统/租金/代币/ATA/元数据程序
     #[account(address = system_program::ID)]
     system_program: Program<'info, System>,
@@ -122,54 +177,66 @@ pub struct CreateMarket<'info> {
 }
 
 impl<'info> CreateMarket<'info> {
-    pub fn handler(&mut self, params: CreateMarketParams, global_vault_bump: u8) -> Result<()> {
-        let global_config = &self.global_config;
+    /// 初始化单侧结果代币（0=YES，1=NO）：创建金库ATA、铸造总供应、创建元数据并撤销铸造权限
+    fn init_outcome_token(
+        &self,
+        token_type: u8,
+        symbol: String,
+        uri: String,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
         let creator = &self.creator;
-        let yes_token: &Box<Account<'info, Mint>> = &self.yes_token;
-        let no_token: &Box<Account<'info, Mint>> = &self.no_token;
         let global_vault = &self.global_vault;
-        let global_yes_token_account = &self.global_yes_token_account;
+        let (mint, metadata_account, global_token_account, name) = if token_type == 0 {
+            (
+                self.yes_token.to_account_info(),
+                self.yes_token_metadata_account.to_account_info(),
+                self.global_yes_token_account.to_account_info(),
+                YES_NAME,
+            )
+        } else {
+            (
+                self.no_token.to_account_info(),
+                self.no_token_metadata_account.to_account_info(),
+                self.global_no_token_account.to_account_info(),
+                NO_NAME,
+            )
+        };
 
-        // 1) 创建全局金库的 YES ATA
+        // 1) 创建全局金库的 ATA
         associated_token::create(CpiContext::new(
             self.associated_token_program.to_account_info(),
             associated_token::Create {
                 payer: creator.to_account_info(),
-                associated_token: global_yes_token_account.to_account_info(),
+                associated_token: global_token_account.clone(),
                 authority: global_vault.to_account_info(),
-                mint: yes_token.to_account_info(),
+                mint: mint.clone(),
                 token_program: self.token_program.to_account_info(),
                 system_program: self.system_program.to_account_info(),
             },
         ))?;
 
-        // PDA签名 seeds（global vault 作为 mint authority）
-        let signer_seeds: &[&[&[u8]]] = &[&[
-            GLOBAL.as_bytes(),
-            &[global_vault_bump],
-        ]];
-
-        // 2) 铸造 YES 代币到全局金库 ATA（总供应）
+        // 2) 铸造总供应到全局金库 ATA
         token::mint_to(
             CpiContext::new_with_signer(
                 self.token_program.to_account_info(),
                 token::MintTo {
-                    mint: yes_token.to_account_info(),
-                    to: global_yes_token_account.to_account_info(),
+                    mint: mint.clone(),
+                    to: global_token_account,
                     authority: global_vault.to_account_info(),
                 },
                 signer_seeds,
             ),
-            global_config.token_supply_config,
+            self.global_config.token_supply_config,
         )?;
 
-        // 3) 创建 YES 元数据（NO 元数据在 mint_no_token 指令已创建）
+        // 3) 创建元数据
         metadata::create_metadata_accounts_v3(
             CpiContext::new_with_signer(
                 self.mpl_token_metadata_program.to_account_info(),
                 metadata::CreateMetadataAccountsV3 {
-                    metadata: self.yes_token_metadata_account.to_account_info(),
-                    mint: yes_token.to_account_info(),
+                    metadata: metadata_account,
+                    mint: mint.clone(),
                     mint_authority: global_vault.to_account_info(),
                     payer: creator.to_account_info(),
                     update_authority: global_vault.to_account_info(),
@@ -179,9 +246,9 @@ impl<'info> CreateMarket<'info> {
                 signer_seeds,
             ),
             DataV2 {
-                name: YES_NAME.to_string(),
-                symbol: params.yes_symbol,
-                uri: params.yes_uri,
+                name: name.to_string(),
+                symbol,
+                uri,
                 seller_fee_basis_points: 0,
                 creators: None,
                 collection: None,
@@ -192,13 +259,13 @@ impl<'info> CreateMarket<'info> {
             None,
         )?;
 
-        // 4) 撤销 YES mint 的铸造权限（禁止再次铸造）
+        // 4) 撤销 mint 的铸造权限（禁止再次铸造）
         token::set_authority(
             CpiContext::new_with_signer(
                 self.token_program.to_account_info(),
                 token::SetAuthority {
                     current_authority: global_vault.to_account_info(),
-                    account_or_mint: yes_token.to_account_info(),
+                    account_or_mint: mint,
                 },
                 signer_seeds,
             ),
@@ -206,8 +273,135 @@ impl<'info> CreateMarket<'info> {
             None,
         )?;
 
+        Ok(())
+    }
+
+    /// 创建YES/NO两侧代币与市场账户，任一步失败则整笔交易回滚
+    pub fn handler(
+        &mut self,
+        params: CreateMarketParams,
+        global_vault_bump: u8,
+        market_metadata_bump: u8,
+    ) -> Result<()> {
+        require!(!self.global_config.paused, PredictionMarketError::Paused);
+
+        // 开启白名单时仅允许白名单中的创建者
+        if self.global_config.whitelist_enabled {
+            require!(self.whitelist.is_some(), PredictionMarketError::NotWhiteList);
+        }
+
+        // 收取市场创建费（白名单创建者可按配置免收）
+        let waived = self.global_config.whitelist_fee_waiver && self.whitelist.is_some();
+        let creation_fee = if waived { 0 } else { self.global_config.market_creation_fee };
+        if creation_fee > 0 {
+            sol_transfer_from_user(
+                &self.creator,
+                self.team_wallet.to_account_info(),
+                &self.system_program,
+                creation_fee,
+            )?;
+        }
+
+        // 创建者选择的LP费率与创建者费率需在管理员设定的范围内
+        let lp_buy_fee = params.lp_buy_fee.unwrap_or(self.global_config.lp_buy_fee);
+        let lp_sell_fee = params.lp_sell_fee.unwrap_or(self.global_config.lp_sell_fee);
+        self.global_config.lp_buy_fee_config.validate(&lp_buy_fee)?;
+        self.global_config.lp_sell_fee_config.validate(&lp_sell_fee)?;
+        let creator_fee = params.creator_fee.unwrap_or(0);
+        self.global_config.creator_fee_config.validate(&creator_fee)?;
+
+        // 买卖两个方向的手续费合计均不能超过100%
+        for (platform_fee, lp_fee) in [
+            (self.global_config.platform_buy_fee, lp_buy_fee),
+            (self.global_config.platform_sell_fee, lp_sell_fee),
+        ] {
+            let total_fee = platform_fee
+                .checked_add(lp_fee)
+                .and_then(|v| v.checked_add(creator_fee))
+                .ok_or(PredictionMarketError::OverflowOrUnderflowOccurred)?;
+            require!(total_fee <= FEE_BASIS_POINTS, PredictionMarketError::FeeTooHigh);
+        }
+
+        // 锁定创建者保证金
+        let creator_bond = self.global_config.creator_bond;
+        if creator_bond > 0 {
+            sol_transfer_from_user(
+                &self.creator,
+                self.bond_escrow.to_account_info(),
+                &self.system_program,
+                creator_bond,
+            )?;
+        }
+
+        // 时间校验：开始槽位不能在过去或超过最大延迟，结束槽位必须晚于开始槽位（或当前槽位）
+        let current_slot = Clock::get()?.slot;
+        if let Some(start_slot) = params.start_slot {
+            require!(
+                start_slot >= current_slot
+                    && start_slot <= current_slot.saturating_add(MAX_START_SLOT_DELAY),
+                PredictionMarketError::InvalidStartTime
+            );
+        }
+        if let Some(ending_slot) = params.ending_slot {
+            require!(
+                ending_slot > params.start_slot.unwrap_or(current_slot),
+                PredictionMarketError::InvalidEndTime
+            );
+        }
+
+        // 时间戳校验：规则同槽位，另要求可结算时间不早于结束时间
+        let now = Clock::get()?.unix_timestamp;
+        if let Some(start_timestamp) = params.start_timestamp {
+            require!(
+                start_timestamp >= now
+                    && start_timestamp <= now.saturating_add(MAX_START_TIME_DELAY),
+                PredictionMarketError::InvalidStartTime
+            );
+        }
+        if let Some(ending_timestamp) = params.ending_timestamp {
+            require!(
+                ending_timestamp > params.start_timestamp.unwrap_or(now),
+                PredictionMarketError::InvalidEndTime
+            );
+        }
+        if let Some(resolution_timestamp) = params.resolution_timestamp {
+            require!(
+                resolution_timestamp >= params.ending_timestamp.unwrap_or(now),
+                PredictionMarketError::InvalidEndTime
+            );
+        }
+
+        // 市场元数据校验：问题不能为空，文本长度不超过账户预留空间
+        require!(!params.question.is_empty(), PredictionMarketError::InvalidParameter);
+        require!(
+            params.question.len() <= MarketMetadata::MAX_QUESTION_LEN
+                && params.category.len() <= MarketMetadata::MAX_CATEGORY_LEN
+                && params.resolution_source.len() <= MarketMetadata::MAX_RESOLUTION_SOURCE_LEN,
+            PredictionMarketError::ValueTooLarge
+        );
+        // 市场ID由问题与规则确定，调用方不能任意选择
+        require!(
+            params.market_id == MarketMetadata::market_id(&params.question, &params.rules_hash),
+            PredictionMarketError::InvalidMarketId
+        );
+
+        // PDA签名 seeds（global vault 作为 mint authority）
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            GLOBAL.as_bytes(),
+            &[global_vault_bump],
+        ]];
+
+        self.init_outcome_token(0, params.yes_symbol, params.yes_uri, signer_seeds)?;
+        self.init_outcome_token(1, params.no_symbol, params.no_uri, signer_seeds)?;
+
+        let global_config = &self.global_config;
+        let creator = &self.creator;
+        let yes_token = &self.yes_token;
+        let no_token = &self.no_token;
+
         // 5) 初始化市场账户状态
         let market = &mut self.market;
+        market.market_id = params.market_id;
         market.yes_token_mint = yes_token.key();
         market.no_token_mint = no_token.key();
         market.creator = creator.key();
@@ -227,19 +421,67 @@ impl<'info> CreateMarket<'info> {
         // 初始 SOL 储备为 0（后续通过 add_liquidity 注入）
         market.real_yes_sol_reserves = 0;
         market.real_no_sol_reserves = 0;
+        market.yes_trade_sol = 0;
+        market.no_trade_sol = 0;
+
+        // 联合曲线虚拟储备（YES/NO 两侧各自独立定价）
+        market.virtual_yes_sol_reserves = global_config.initial_virtual_sol_reserves_config;
+        market.virtual_yes_token_reserves = global_config.initial_virtual_token_reserves_config;
+        market.virtual_no_sol_reserves = global_config.initial_virtual_sol_reserves_config;
+        market.virtual_no_token_reserves = global_config.initial_virtual_token_reserves_config;
 
         // 市场状态
         market.is_completed = false;
+        market.winning_token_type = 0;
+        market.is_yes_curve_completed = false;
+        market.is_no_curve_completed = false;
+        market.is_yes_migrated = false;
+        market.is_no_migrated = false;
         market.start_slot = params.start_slot;
         market.ending_slot = params.ending_slot;
+        market.start_timestamp = params.start_timestamp;
+        market.ending_timestamp = params.ending_timestamp;
+        market.resolution_timestamp = params.resolution_timestamp;
 
-        market.lps = Vec::new();
+        market.lp_yes_share = 0;
+        market.lp_no_share = 0;
+        market.lp_yes_token_basis = 0;
+        market.lp_no_token_basis = 0;
         market.total_lp_amount = 0;
+        market.total_lp_shares = 0;
+        market.staked_lp_shares = 0;
+        market.lp_fee_per_share = 0;
+        market.lp_fee_reserve = 0;
+        market.lp_escrow_sol = 0;
+        market.lp_escrow_yes_tokens = 0;
+        market.lp_escrow_no_tokens = 0;
+        // 快照费率（平台费取自当前配置，LP费取创建者选择值），后续配置变更只影响新市场
+        market.platform_buy_fee = global_config.platform_buy_fee;
+        market.platform_sell_fee = global_config.platform_sell_fee;
+        market.lp_buy_fee = lp_buy_fee;
+        market.lp_sell_fee = lp_sell_fee;
+        market.creator_fee = creator_fee;
+        market.creator_fee_reserve = 0;
+
+        market.creator_bond = creator_bond;
+        market.is_bond_settled = false;
+        market.paused = false;
+
+        // 6) 记录市场问题与结算规则
+        let market_metadata = &mut self.market_metadata;
+        market_metadata.market = market.key();
+        market_metadata.question = params.question;
+        market_metadata.rules_hash = params.rules_hash;
+        market_metadata.category = params.category;
+        market_metadata.resolution_source = params.resolution_source;
+        market_metadata.created_at = Clock::get()?.unix_timestamp;
+        market_metadata.bump = market_metadata_bump;
 
-        // 6) 触发创建事件（Option 转换为 0 表示未设置）
+        // 7) 触发创建事件（Option 转换为 0 表示未设置）
         emit!(CreateEvent {
             creator: creator.key(),
             market: market.key(),
+            market_id: market.market_id,
             token_yes: yes_token.key(),
             metadata_yes: self.yes_token_metadata_account.key(),
             token_yes_total_supply: market.token_yes_total_supply,
@@ -250,6 +492,9 @@ impl<'info> CreateMarket<'info> {
             real_no_sol_reserves: market.real_no_sol_reserves,
             start_slot: market.start_slot.unwrap_or(0),
             ending_slot: market.ending_slot.unwrap_or(0),
+            start_timestamp: market.start_timestamp.unwrap_or(0),
+            ending_timestamp: market.ending_timestamp.unwrap_or(0),
+            resolution_timestamp: market.resolution_timestamp.unwrap_or(0),
         });
 
         msg!("CreateMarket completed: market={}, yes_mint={}, no_mint={}",
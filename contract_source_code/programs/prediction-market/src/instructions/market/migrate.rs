@@ -0,0 +1,247 @@
+//! 市场指令：毕业迁移（将已完成曲线的代币与SOL通过CPI注入外部AMM池）
+
+use crate::{
+    constants::{AMM_INITIALIZE_POOL_DISCRIMINATOR, CONFIG, GLOBAL, MARKET, MIGRATION},
+    errors::PredictionMarketError,
+    state::{config::*, market::*},
+    utils::{sol_transfer_with_signer, token_transfer_with_signer},
+};
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, program::invoke_signed},
+    system_program,
+};
+use anchor_spl::{
+    associated_token::{self, AssociatedToken},
+    token::{self, Mint, Token, TokenAccount},
+};
+
+/// 账户集合：迁移所需账户
+///
+/// 外部AMM池所需的其余账户（池状态、池金库、LP mint等）通过 remaining_accounts 按顺序透传
+#[derive(Accounts)]
+pub struct Migrate<'info> {
+    /// 全局配置
+    #[account(
+        seeds = [CONFIG.as_bytes()],
+        bump,
+    )]
+    global_config: Box<Account<'info, Config>>,
+
+    /// 市场账户
+    #[account(
+        mut,
+        seeds = [MARKET.as_bytes(), &yes_token.key().to_bytes(), &no_token.key().to_bytes()],
+        bump
+    )]
+    market: Account<'info, Market>,
+
+    /// 全局金库（PDA，存放所有市场的SOL；不参与外部CPI签名）
+    /// CHECK: global vault pda which stores SOL
+    #[account(
+        mut,
+        seeds = [GLOBAL.as_bytes()],
+        bump,
+    )]
+    pub global_vault: AccountInfo<'info>,
+
+    /// YES/NO 代币mint
+    pub yes_token: Box<Account<'info, Mint>>,
+    pub no_token: Box<Account<'info, Mint>>,
+
+    /// 全局金库的YES/NO ATA
+    #[account(
+        mut,
+        associated_token::mint = yes_token,
+        associated_token::authority = global_vault,
+    )]
+    pub global_yes_ata: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = no_token,
+        associated_token::authority = global_vault,
+    )]
+    pub global_no_ata: Box<Account<'info, TokenAccount>>,
+
+    /// 迁移托管（PDA，按市场派生的系统账户，作为外部AMM建池CPI的签名者）
+    /// 只持有本次迁移的SOL与代币，外部程序无法触及全局金库
+    /// CHECK: migration escrow pda which only holds the funds being migrated
+    #[account(
+        mut,
+        seeds = [MIGRATION.as_bytes(), &market.key().to_bytes()],
+        bump,
+    )]
+    pub migration_authority: AccountInfo<'info>,
+
+    /// 迁移托管的YES/NO ATA（按需创建）
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = yes_token,
+        associated_token::authority = migration_authority,
+    )]
+    pub migration_yes_ata: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = no_token,
+        associated_token::authority = migration_authority,
+    )]
+    pub migration_no_ata: Box<Account<'info, TokenAccount>>,
+
+    /// 迁移权限（必须为全局authority）
+    #[account(
+        mut,
+        constraint = global_config.authority == authority.key() @PredictionMarketError::InvalidMigrationAuthority
+    )]
+    pub authority: Signer<'info>,
+
+    /// 外部AMM程序（需与配置一致）
+    /// CHECK: checked against the address in the global_config
+    #[account(
+        executable,
+        constraint = global_config.amm_program == amm_program.key() @PredictionMarketError::InvalidMeteoraProgram
+    )]
+    pub amm_program: UncheckedAccount<'info>,
+
+    /// 系统/代币/ATA程序
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+    #[account(address = associated_token::ID)]
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> Migrate<'info> {
+    /// 校验曲线已完成且未迁移，将该侧代币与真实SOL经迁移托管注入外部AMM池
+    pub fn handler(
+        &mut self,
+        token_type: u8,
+        remaining_accounts: &[AccountInfo<'info>],
+        global_vault_bump: u8,
+        migration_authority_bump: u8,
+    ) -> Result<()> {
+        require!(token_type <= 1, PredictionMarketError::InvalidArgument);
+        require!(
+            self.market.is_curve_completed(token_type),
+            PredictionMarketError::CurveNotCompleted
+        );
+        require!(
+            !self.market.is_migrated(token_type),
+            PredictionMarketError::AmmAlreadyExists
+        );
+        // 迁移会清空该侧真实储备：先将LP在该侧的流动性划入LP托管额度，LP之后按份额领取
+        self.market
+            .escrow_side_liquidity(token_type)
+            .ok_or(PredictionMarketError::ArithmeticError)?;
+        let escrow_tokens = if token_type == 0 {
+            self.market.lp_escrow_yes_tokens
+        } else {
+            self.market.lp_escrow_no_tokens
+        };
+
+        let (token_mint, global_ata, migration_ata) = if token_type == 0 {
+            (&self.yes_token, &self.global_yes_ata, &mut self.migration_yes_ata)
+        } else {
+            (&self.no_token, &self.global_no_ata, &mut self.migration_no_ata)
+        };
+
+        // 金库中该侧除LP托管额度外的全部代币与该侧真实SOL储备
+        let token_amount = global_ata
+            .amount
+            .checked_sub(escrow_tokens)
+            .ok_or(PredictionMarketError::OverflowOrUnderflowOccurred)?;
+        let (_, _, sol_amount, _) = self.market.curve_reserves(token_type);
+
+        // 由本程序将恰好的数量从全局金库转入迁移托管
+        let global_vault_seeds: &[&[&[u8]]] = &[&[GLOBAL.as_bytes(), &[global_vault_bump]]];
+        let escrow_lamports_before = self.migration_authority.lamports();
+        let escrow_tokens_before = migration_ata.amount;
+        if token_amount > 0 {
+            token_transfer_with_signer(
+                global_ata.to_account_info(),
+                self.global_vault.to_account_info(),
+                migration_ata.to_account_info(),
+                &self.token_program,
+                global_vault_seeds,
+                token_amount,
+            )?;
+        }
+        if sol_amount > 0 {
+            sol_transfer_with_signer(
+                self.global_vault.to_account_info(),
+                self.migration_authority.to_account_info(),
+                &self.system_program,
+                global_vault_seeds,
+                sol_amount,
+            )?;
+        }
+
+        // 构造 initialize_pool(token_amount, sol_amount) CPI，由迁移托管签名
+        let mut data = AMM_INITIALIZE_POOL_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&token_amount.to_le_bytes());
+        data.extend_from_slice(&sol_amount.to_le_bytes());
+
+        let mut accounts = vec![
+            AccountMeta::new(self.migration_authority.key(), true),
+            AccountMeta::new(migration_ata.key(), false),
+            AccountMeta::new_readonly(token_mint.key(), false),
+            AccountMeta::new_readonly(self.token_program.key(), false),
+            AccountMeta::new_readonly(self.system_program.key(), false),
+        ];
+        let mut account_infos = vec![
+            self.migration_authority.to_account_info(),
+            migration_ata.to_account_info(),
+            token_mint.to_account_info(),
+            self.token_program.to_account_info(),
+            self.system_program.to_account_info(),
+        ];
+        for account in remaining_accounts {
+            accounts.push(if account.is_writable {
+                AccountMeta::new(account.key(), account.is_signer)
+            } else {
+                AccountMeta::new_readonly(account.key(), account.is_signer)
+            });
+            account_infos.push(account.clone());
+        }
+        account_infos.push(self.amm_program.to_account_info());
+
+        let market_key = self.market.key();
+        let migration_seeds: &[&[&[u8]]] = &[&[
+            MIGRATION.as_bytes(),
+            market_key.as_ref(),
+            &[migration_authority_bump],
+        ]];
+
+        invoke_signed(
+            &Instruction {
+                program_id: self.amm_program.key(),
+                accounts,
+                data,
+            },
+            &account_infos,
+            migration_seeds,
+        )?;
+
+        // 外部AMM必须恰好取走本次迁移的SOL与代币
+        migration_ata.reload()?;
+        require!(
+            self.migration_authority.lamports() == escrow_lamports_before
+                && migration_ata.amount == escrow_tokens_before,
+            PredictionMarketError::MigrationAmountMismatch
+        );
+
+        self.market.mark_migrated(token_type);
+
+        msg!(
+            "Migrate completed: market={}, mint={}, token_amount={}, sol_amount={}",
+            self.market.key(),
+            token_mint.key(),
+            token_amount,
+            sol_amount
+        );
+
+        Ok(())
+    }
+}
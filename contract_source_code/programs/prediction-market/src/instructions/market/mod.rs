@@ -1,6 +1,12 @@
 pub mod add_liquidity;
+pub mod claim_creator_fees;
+pub mod claim_lp_fees;
 pub mod create_market;
-pub mod mint_no_token;
+pub mod migrate;
+pub mod redeem;
 pub mod resolution;
+pub mod settle_creator_bond;
+pub mod stake_lp;
 pub mod swap;
+pub mod unstake_lp;
 pub mod withdraw_liquidity;
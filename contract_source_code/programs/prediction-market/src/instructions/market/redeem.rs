@@ -0,0 +1,122 @@
+//! 市场指令：结算后赎回结果代币（不受暂停影响，保证用户始终可以退出）
+
+use crate::{
+    constants::{GLOBAL, MARKET},
+    errors::PredictionMarketError,
+    state::market::*,
+    utils::{sol_transfer_with_signer, token_transfer_user},
+};
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::{
+    associated_token::{self, AssociatedToken},
+    token::{self, Mint, Token, TokenAccount},
+};
+
+/// 账户集合：赎回结果代币所需账户
+#[derive(Accounts)]
+#[instruction(amount: u64, token_type: u8)]
+pub struct Redeem<'info> {
+    /// 市场账户
+    #[account(
+        mut,
+        seeds = [MARKET.as_bytes(), &yes_token.key().to_bytes(), &no_token.key().to_bytes()],
+        bump
+    )]
+    market: Account<'info, Market>,
+
+    /// 全局金库（PDA，存放SOL）
+    /// CHECK: global vault pda which stores SOL
+    #[account(
+        mut,
+        seeds = [GLOBAL.as_bytes()],
+        bump,
+    )]
+    pub global_vault: AccountInfo<'info>,
+
+    /// YES/NO 代币mint
+    pub yes_token: Box<Account<'info, Mint>>,
+    pub no_token: Box<Account<'info, Mint>>,
+
+    /// 被赎回代币的mint（按 token_type 必须为 YES 或 NO）
+    #[account(
+        constraint = token_mint.key() == if token_type == 0 { yes_token.key() } else { no_token.key() }
+            @PredictionMarketError::InvalidArgument
+    )]
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    /// 全局金库中被赎回代币的ATA（接收赎回的代币）
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = global_vault,
+    )]
+    pub global_ata: Box<Account<'info, TokenAccount>>,
+
+    /// 用户被赎回代币的ATA
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = user,
+    )]
+    pub user_ata: Box<Account<'info, TokenAccount>>,
+
+    /// 用户签名者
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// 系统/代币/ATA程序
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+    #[account(address = associated_token::ID)]
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> Redeem<'info> {
+    /// 处理赎回：由市场按结算结果计算应付SOL，支付后将代币转回全局金库
+    pub fn handler(&mut self, amount: u64, token_type: u8, global_vault_bump: u8) -> Result<()> {
+        require!(
+            amount <= self.user_ata.amount,
+            PredictionMarketError::InsufficientTokens
+        );
+
+        let signer_seeds: &[&[&[u8]]] = &[&[GLOBAL.as_bytes(), &[global_vault_bump]]];
+
+        // 赢家份额按转入前的金库余额计算
+        let winning_vault_balance = self.global_ata.amount;
+        let sol_out = self.market.redeem(amount, token_type, winning_vault_balance)?;
+
+        // 全局金库必须保持免租
+        let rent_exempt = Rent::get()?.minimum_balance(0);
+        require!(
+            self.global_vault.lamports().saturating_sub(sol_out) >= rent_exempt,
+            PredictionMarketError::InsufficientSol
+        );
+
+        sol_transfer_with_signer(
+            self.global_vault.to_account_info(),
+            self.user.to_account_info(),
+            &self.system_program,
+            signer_seeds,
+            sol_out,
+        )?;
+        token_transfer_user(
+            self.user_ata.to_account_info(),
+            &self.user,
+            self.global_ata.to_account_info(),
+            &self.token_program,
+            amount,
+        )?;
+
+        msg!(
+            "Redeem: user={}, token_type={}, amount={}, sol_out={}",
+            self.user.key(),
+            token_type,
+            amount,
+            sol_out
+        );
+
+        Ok(())
+    }
+}
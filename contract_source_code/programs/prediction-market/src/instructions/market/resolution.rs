@@ -1,7 +1,7 @@
 //! 市场指令：结算市场（由管理员触发）
 
 use crate::{
-    constants::{CONFIG, GLOBAL, MARKET, USERINFO},
+    constants::{CONFIG, GLOBAL, INVALID_OUTCOME, MARKET},
     errors::PredictionMarketError,
     state::{config::*, market::*},
 };
@@ -43,19 +43,7 @@ pub struct Resolution<'info> {
     pub yes_token: Box<Account<'info, Mint>>,
     pub no_token: Box<Account<'info, Mint>>,
 
-    /// 用户信息（领取奖励时用）
-    #[account(
-        mut,
-        seeds = [USERINFO.as_bytes(), &user.key().to_bytes(), &market.key().to_bytes()],
-        bump
-    )]
-    pub user_info: Box<Account<'info, UserInfo>>,
-
-    /// 用户（用于接收奖励等场景）
-    #[account(mut)]
-    pub user: AccountInfo<'info>,
-
-    /// 管理员（必须为全局authority）
+    /// 结算员（必须为全局配置中的 resolver）
     #[account(mut)]
     pub authority: Signer<'info>,
 
@@ -69,31 +57,40 @@ pub struct Resolution<'info> {
 }
 
 impl<'info> Resolution<'info>{
-    /// 结算入口；当前为权限校验与占位逻辑
-    pub fn handler(&mut self, _yes_amount: u64, _no_amount: u64 ,token_type: u8, is_completed: bool ,global_vault_bump:u8)-> Result<()> {
-        // 仅限管理员
+    /// 结算入口：校验权限与时间后写入结算结果，持有者随后通过 redeem 赎回
+    pub fn handler(&mut self, _yes_amount: u64, _no_amount: u64 ,token_type: u8, is_completed: bool)-> Result<()> {
+        // 仅限结算员
         require!(
-            self.authority.key() == self.global_config.authority.key(),
+            self.authority.key() == self.global_config.resolver,
             PredictionMarketError::InvalidMigrationAuthority
         );
 
-        let signer_seeds: &[&[&[u8]]] = &[&[
-            GLOBAL.as_bytes(),
-            &[global_vault_bump],
-        ]];
+        if is_completed {
+            // 结算结果：0=YES，1=NO，2=无效（罚没创建者保证金）；结算后不可更改
+            require!(!self.market.is_completed, PredictionMarketError::MarketIsCompleted);
+            require!(
+                token_type <= INVALID_OUTCOME,
+                PredictionMarketError::RESOLUTIONTOKEYTYPEERROR
+            );
 
-        // 兑付 + 完成
-        self.market.resolution(
-            &mut self.global_vault.to_account_info(),
-            &mut self.user,
-            signer_seeds,
-            &mut self.user_info,
-            token_type,
-            &self.system_program,
-        )?;
+            // 交易窗口结束（且到达可结算时间）后才能结算
+            let clock = Clock::get()?;
+            if let Some(ending_slot) = self.market.ending_slot {
+                require!(clock.slot >= ending_slot, PredictionMarketError::ResolutionTooEarly);
+            }
+            if let Some(eligible_at) = self
+                .market
+                .resolution_timestamp
+                .or(self.market.ending_timestamp)
+            {
+                require!(
+                    clock.unix_timestamp >= eligible_at,
+                    PredictionMarketError::ResolutionTooEarly
+                );
+            }
 
-        if is_completed {
             self.market.is_completed = true;
+            self.market.winning_token_type = token_type;
         }
 
         Ok(())
@@ -0,0 +1,109 @@
+//! 市场指令：结算创建者保证金（市场结算后返还创建者或罚没至手续费金库）
+
+use crate::{
+    constants::{CREATOR_BOND, INVALID_OUTCOME, MARKET},
+    errors::PredictionMarketError,
+    events::BondSettledEvent,
+    state::{fee_vault::FeeVault, market::*},
+    utils::sol_transfer_with_signer,
+};
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::token::Mint;
+
+/// 账户集合：结算创建者保证金所需账户（任何人均可触发）
+#[derive(Accounts)]
+pub struct SettleCreatorBond<'info> {
+    /// 市场账户
+    #[account(
+        mut,
+        seeds = [MARKET.as_bytes(), &yes_token.key().to_bytes(), &no_token.key().to_bytes()],
+        bump
+    )]
+    market: Account<'info, Market>,
+
+    /// YES/NO 代币mint
+    pub yes_token: Box<Account<'info, Mint>>,
+    pub no_token: Box<Account<'info, Mint>>,
+
+    /// 创建者保证金托管（PDA，存放SOL）
+    /// CHECK: bond escrow pda which stores SOL
+    #[account(
+        mut,
+        seeds = [CREATOR_BOND.as_bytes(), market.key().as_ref()],
+        bump,
+    )]
+    pub bond_escrow: AccountInfo<'info>,
+
+    /// 市场创建者（保证金返还对象）
+    /// CHECK: should be same with the creator stored in the market
+    #[account(
+        mut,
+        constraint = market.creator == creator.key() @PredictionMarketError::IncorrectAuthority
+    )]
+    pub creator: AccountInfo<'info>,
+
+    /// 手续费金库（PDA，保证金罚没对象；罚没金额划归金库管理员，不参与按权重分配）
+    #[account(
+        mut,
+        seeds = [FeeVault::SEED_PREFIX.as_bytes()],
+        bump = fee_vault.bump,
+    )]
+    pub fee_vault: Box<Account<'info, FeeVault>>,
+
+    /// 系统程序
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SettleCreatorBond<'info> {
+    /// 市场结算后转出托管中的保证金：无效市场罚没至手续费金库并记入金库管理员份额，否则返还创建者
+    pub fn handler(&mut self, bond_escrow_bump: u8) -> Result<()> {
+        require!(self.market.is_completed, PredictionMarketError::MarketNotCompleted);
+        require!(!self.market.is_bond_settled, PredictionMarketError::BondAlreadySettled);
+
+        let slashed = self.market.winning_token_type == INVALID_OUTCOME;
+        let recipient = if slashed {
+            self.fee_vault.to_account_info()
+        } else {
+            self.creator.to_account_info()
+        };
+        let amount = self.bond_escrow.lamports();
+
+        if amount > 0 {
+            let market_key = self.market.key();
+            let signer_seeds: &[&[&[u8]]] = &[&[
+                CREATOR_BOND.as_bytes(),
+                market_key.as_ref(),
+                &[bond_escrow_bump],
+            ]];
+
+            sol_transfer_with_signer(
+                self.bond_escrow.to_account_info(),
+                recipient.clone(),
+                &self.system_program,
+                signer_seeds,
+                amount,
+            )?;
+
+            if slashed {
+                self.fee_vault.treasury_fees = self
+                    .fee_vault
+                    .treasury_fees
+                    .checked_add(amount)
+                    .ok_or(PredictionMarketError::OverflowOrUnderflowOccurred)?;
+            }
+        }
+
+        self.market.is_bond_settled = true;
+
+        emit!(BondSettledEvent {
+            market: self.market.key(),
+            creator: self.market.creator,
+            recipient: recipient.key(),
+            amount,
+            slashed,
+        });
+
+        Ok(())
+    }
+}
@@ -3,7 +3,7 @@
 use crate::{
     constants::{CONFIG, GLOBAL, MARKET, USERINFO},
     errors::PredictionMarketError,
-    state::{config::*, market::*},
+    state::{config::*, fee_vault::FeeVault, market::*},
 };
 use anchor_lang::{prelude::*, system_program};
 use anchor_spl::{
@@ -22,13 +22,13 @@ pub struct Swap<'info> {
     )]
     global_config: Box<Account<'info, Config>>,
 
-    /// 团队钱包（收取平台费，需与配置一致）
-    /// CHECK: should be same with the address in the global_config
+    /// 手续费金库（PDA，归集平台手续费）
     #[account(
         mut,
-        constraint = global_config.team_wallet == team_wallet.key() @PredictionMarketError::IncorrectAuthority
+        seeds = [FeeVault::SEED_PREFIX.as_bytes()],
+        bump = fee_vault.bump,
     )]
-    pub team_wallet: AccountInfo<'info>,
+    pub fee_vault: Box<Account<'info, FeeVault>>,
 
     /// 市场账户
     #[account(
@@ -129,25 +129,66 @@ pub struct Swap<'info> {
 }
 
 impl<'info> Swap<'info> { 
-    /// 处理交易：校验市场时间/状态，准备用户账户，委托给 `Market::swap`
+    /// 处理交易：校验市场时间/状态与曲线阶段，准备用户账户，委托给 `Market::swap`
     pub fn handler(&mut self, amount: u64, direction: u8, token_type: u8 ,minimum_receive_amount: u64, global_vault_bump:u8) -> Result<()> {
         let market = &mut self.market;
         
-        // 校验结束时间
+        // 校验开始/结束时间（结束时刻起停止交易，与结算允许的时刻不重叠）
         let clock = Clock::get()?;
+        if let Some(start_slot) = market.start_slot {
+            require!(
+                clock.slot >= start_slot,
+                PredictionMarketError::MarketNotStarted
+            )
+        }
         if let Some(ending_slot) = market.ending_slot {
             require!(
-                ending_slot >= clock.slot,
+                clock.slot < ending_slot,
+                PredictionMarketError::InvalidEndTime
+            )
+        }
+        if let Some(start_timestamp) = market.start_timestamp {
+            require!(
+                clock.unix_timestamp >= start_timestamp,
+                PredictionMarketError::MarketNotStarted
+            )
+        }
+        if let Some(ending_timestamp) = market.ending_timestamp {
+            require!(
+                clock.unix_timestamp < ending_timestamp,
                 PredictionMarketError::InvalidEndTime
             )
         }
 
+        // 平台或市场暂停时禁止交易
+        require!(
+            !self.global_config.paused && !market.paused,
+            PredictionMarketError::Paused
+        );
+
         // 不能在完成后再交易
         require!(
             market.is_completed == false,
             PredictionMarketError::CurveAlreadyCompleted
         );
 
+        // 参数校验：方向 0=买入/1=卖出，代币类型 0=YES/1=NO
+        require!(direction <= 1, PredictionMarketError::InvalidArgument);
+        require!(token_type <= 1, PredictionMarketError::InvalidArgument);
+        require!(amount > 0, PredictionMarketError::InvalidAmount);
+
+        // 该侧联合曲线毕业后停止交易（等待迁移）
+        require!(
+            !market.is_curve_completed(token_type),
+            PredictionMarketError::CurveAlreadyCompleted
+        );
+
+        // LP注入的SOL流动性达到配置的最小值后才开放交易
+        require!(
+            market.total_lp_amount >= self.global_config.min_sol_liquidity,
+            PredictionMarketError::InsufficientMarketLiquidity
+        );
+
         let user_info_pda = &mut self.user_info;
 
         // 初始化用户信息（如未初始化）
@@ -164,7 +205,7 @@ impl<'info> Swap<'info> {
         }
 
         let source = &mut self.global_vault.to_account_info();
-        let team_wallet = &mut self.team_wallet;
+        let fee_vault = &mut self.fee_vault.to_account_info();
 
         let yes_token = &mut self.yes_token;
         let user_yes_ata = &mut self.user_yes_ata;
@@ -181,8 +222,8 @@ impl<'info> Swap<'info> {
             global_vault_bump
         );
 
-        // 确保用户ATA存在
-        if token_type == 0 {
+        // 确保用户ATA存在（为本次交易的代币类型创建）
+        if token_type == 1 {
             // NO ATA
             if user_no_ata.data_is_empty() {
                 anchor_spl::associated_token::create(CpiContext::new(
@@ -230,7 +271,7 @@ impl<'info> Swap<'info> {
             &mut self.global_no_ata,
             user_no_ata,
             source,
-            team_wallet,
+            fee_vault,
             amount,
             direction,
             token_type,
@@ -0,0 +1,113 @@
+//! 市场指令：将LP仓位中的份额代币取出到钱包（取出后可自由转让）
+
+use crate::{
+    constants::{GLOBAL, LP_MINT, MARKET},
+    errors::PredictionMarketError,
+    state::{lp_position::LpPosition, market::*},
+    utils::token_transfer_with_signer,
+};
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::{
+    associated_token::{self, AssociatedToken},
+    token::{self, Mint, Token, TokenAccount},
+};
+
+/// 账户集合：取出LP份额所需账户
+#[derive(Accounts)]
+pub struct UnstakeLp<'info> {
+    /// 市场账户
+    #[account(
+        mut,
+        seeds = [MARKET.as_bytes(), &yes_token.key().to_bytes(), &no_token.key().to_bytes()],
+        bump
+    )]
+    market: Account<'info, Market>,
+
+    /// 全局金库（PDA，LP托管ATA的所有者）
+    /// CHECK: global vault pda which owns the LP custody account
+    #[account(
+        seeds = [GLOBAL.as_bytes()],
+        bump,
+    )]
+    pub global_vault: AccountInfo<'info>,
+
+    /// YES/NO 代币mint
+    pub yes_token: Box<Account<'info, Mint>>,
+    pub no_token: Box<Account<'info, Mint>>,
+
+    /// 市场LP份额mint
+    #[account(
+        seeds = [LP_MINT.as_bytes(), &market.key().to_bytes()],
+        bump,
+    )]
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    /// LP份额托管ATA（取出份额的来源）
+    #[account(
+        mut,
+        associated_token::mint = lp_mint,
+        associated_token::authority = global_vault,
+    )]
+    pub lp_custody: Box<Account<'info, TokenAccount>>,
+
+    /// 用户的LP份额ATA（按需创建）
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = lp_mint,
+        associated_token::authority = user,
+    )]
+    pub user_lp_ata: Box<Account<'info, TokenAccount>>,
+
+    /// LP仓位
+    #[account(
+        mut,
+        seeds = [LpPosition::SEED_PREFIX.as_bytes(), &market.key().to_bytes(), &user.key().to_bytes()],
+        bump = lp_position.bump
+    )]
+    pub lp_position: Box<Account<'info, LpPosition>>,
+
+    /// 用户签名者
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// 系统/代币/ATA程序
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+    #[account(address = associated_token::ID)]
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> UnstakeLp<'info> {
+    /// 从用户的LP仓位中扣减份额，并将对应的LP份额代币从托管ATA转到用户钱包
+    ///
+    /// 取出前已累计的LP手续费结算进仓位，取出的份额不再参与之后的手续费分配
+    pub fn handler(&mut self, shares: u64, global_vault_bump: u8) -> Result<()> {
+        require!(shares > 0, PredictionMarketError::InvalidAmount);
+        require!(
+            shares <= self.lp_position.shares,
+            PredictionMarketError::InsufficientTokens
+        );
+
+        let signer_seeds: &[&[&[u8]]] = &[&[GLOBAL.as_bytes(), &[global_vault_bump]]];
+
+        token_transfer_with_signer(
+            self.lp_custody.to_account_info(),
+            self.global_vault.to_account_info(),
+            self.user_lp_ata.to_account_info(),
+            &self.token_program,
+            signer_seeds,
+            shares,
+        )?;
+
+        self.market
+            .debit_lp_position(&mut self.lp_position, shares)
+            .ok_or(PredictionMarketError::ArithmeticError)?;
+
+        msg!("UnstakeLp: user={}, shares={}", self.user.key(), shares);
+
+        Ok(())
+    }
+}
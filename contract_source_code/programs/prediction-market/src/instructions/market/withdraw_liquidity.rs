@@ -1,14 +1,15 @@
 //! 市场指令：提取流动性（LP提取）
 
 use crate::{
-    constants::{CONFIG, GLOBAL, MARKET, USERINFO},
+    constants::{CONFIG, GLOBAL, LP_MINT, MARKET, USERINFO},
     errors::PredictionMarketError,
-    state::{config::*, market::*},
+    state::{config::*, lp_position::LpPosition, market::*},
+    utils::token_burn_with_signer,
 };
 use anchor_lang::{prelude::*, system_program};
 use anchor_spl::{
     associated_token::{self, AssociatedToken},
-    token::{self, Mint, Token},
+    token::{self, Mint, Token, TokenAccount},
 };
 
 /// 账户集合：提取LP所需账户
@@ -22,14 +23,6 @@ pub struct WithdrawLiquidity<'info> {
     )]
     global_config: Box<Account<'info, Config>>,
 
-    /// 团队钱包（需与配置一致）
-    /// CHECK: should be same with the address in the global_config
-    #[account(
-        mut,
-        constraint = global_config.team_wallet == team_wallet.key() @PredictionMarketError::IncorrectAuthority
-    )]
-    pub team_wallet: AccountInfo<'info>,
-
     /// 市场账户
     #[account(
         mut,
@@ -51,9 +44,65 @@ pub struct WithdrawLiquidity<'info> {
     pub yes_token: Box<Account<'info, Mint>>,
     pub no_token: Box<Account<'info, Mint>>,
 
-    /// 用户信息（需为LP）
+    /// 全局金库的YES/NO ATA（返还代币盈余的来源）
+    #[account(
+        mut,
+        associated_token::mint = yes_token,
+        associated_token::authority = global_vault,
+    )]
+    pub global_yes_ata: Box<Account<'info, TokenAccount>>,
     #[account(
         mut,
+        associated_token::mint = no_token,
+        associated_token::authority = global_vault,
+    )]
+    pub global_no_ata: Box<Account<'info, TokenAccount>>,
+
+    /// 用户的YES/NO ATA（按需创建）
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = yes_token,
+        associated_token::authority = user,
+    )]
+    pub user_yes_ata: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = no_token,
+        associated_token::authority = user,
+    )]
+    pub user_no_ata: Box<Account<'info, TokenAccount>>,
+
+    /// 市场LP份额mint
+    #[account(
+        mut,
+        seeds = [LP_MINT.as_bytes(), &market.key().to_bytes()],
+        bump,
+    )]
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    /// LP份额托管ATA（提取时从中销毁仓位名下的份额）
+    #[account(
+        mut,
+        associated_token::mint = lp_mint,
+        associated_token::authority = global_vault,
+    )]
+    pub lp_custody: Box<Account<'info, TokenAccount>>,
+
+    /// LP仓位（转让得到的份额代币需先经 stake_lp 存入仓位）
+    #[account(
+        mut,
+        seeds = [LpPosition::SEED_PREFIX.as_bytes(), &market.key().to_bytes(), &user.key().to_bytes()],
+        bump = lp_position.bump
+    )]
+    pub lp_position: Box<Account<'info, LpPosition>>,
+
+    /// 用户信息（按需初始化；LP份额代币可转让，持有者无需自己注入过）
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + std::mem::size_of::<UserInfo>(),
         seeds = [USERINFO.as_bytes(), &user.key().to_bytes(), &market.key().to_bytes()],
         bump
     )]
@@ -73,24 +122,88 @@ pub struct WithdrawLiquidity<'info> {
 }
 
 impl<'info> WithdrawLiquidity<'info> { 
-    /// 处理提取LP：校验用户为LP；其余逻辑待实现
-    pub fn handler(&mut self, amount: u64, global_vault_bump:u8) -> Result<()> {
-        require!(self.user_info.is_lp == true, PredictionMarketError::WITHDRAWNOTLPERROR);
-        require!(self.market.is_completed == false, PredictionMarketError::MarketIsCompleted);
+    /// 处理提取LP：销毁仓位名下托管的LP份额代币，按其占总份额的比例返还SOL与代币盈余（结算后按获胜结果估值）
+    ///
+    /// 已累计的LP手续费结算进仓位，由 claim_lp_fees 另行领取
+    pub fn handler(&mut self, shares: u64, global_vault_bump: u8) -> Result<()> {
+        require!(shares > 0, PredictionMarketError::WITHDRAWLIQUIDITYSOLAMOUNTERROR);
+        require!(
+            shares <= self.lp_position.shares && shares <= self.market.total_lp_shares,
+            PredictionMarketError::WITHDRAWLIQUIDITYSOLAMOUNTERROR
+        );
+
+        // 暂停期间仅允许结算后的赎回，保证用户始终可以退出
+        require!(
+            self.market.is_completed || (!self.global_config.paused && !self.market.paused),
+            PredictionMarketError::Paused
+        );
+
+        // 按提取前的池子状态将份额折算为LP注入额度（向下取整，舍入留在池中）
+        let amount = self
+            .market
+            .lp_amount_for_shares(shares)
+            .ok_or(PredictionMarketError::ArithmeticError)?;
         require!(amount > 0, PredictionMarketError::WITHDRAWLIQUIDITYSOLAMOUNTERROR);
 
+        // 交易阶段的市场一旦达到最小流动性，提取不能使其回落到最小值以下
+        // 曲线毕业后不再接受注入，LP可全部退出以便迁移
+        let min_sol_liquidity = self.global_config.min_sol_liquidity;
+        let total_lp_amount = self.market.total_lp_amount;
+        let trading_phase = !self.market.is_completed
+            && !self.market.is_yes_curve_completed
+            && !self.market.is_no_curve_completed;
+        if trading_phase && total_lp_amount >= min_sol_liquidity {
+            require!(
+                total_lp_amount.saturating_sub(amount) >= min_sol_liquidity,
+                PredictionMarketError::InsufficientMarketLiquidity
+            );
+        }
+
         let signer_seeds: &[&[&[u8]]] = &[&[
             GLOBAL.as_bytes(),
             &[global_vault_bump],
         ]];
 
+        token_burn_with_signer(
+            self.lp_mint.to_account_info(),
+            self.lp_custody.to_account_info(),
+            self.global_vault.to_account_info(),
+            &self.token_program,
+            signer_seeds,
+            shares,
+        )?;
+
+        self.market.total_lp_shares = self
+            .market
+            .total_lp_shares
+            .checked_sub(shares)
+            .ok_or(PredictionMarketError::OverflowOrUnderflowOccurred)?;
+        // 份额变化前先结算已累计的LP手续费
+        self.market
+            .debit_lp_position(&mut self.lp_position, shares)
+            .ok_or(PredictionMarketError::ArithmeticError)?;
+        // 注入净额仅作记录，份额可能来自转让
+        self.lp_position.deposit_amount = self.lp_position.deposit_amount.saturating_sub(amount);
+
         self.market.withdraw_liquidity(
+            &mut self.global_yes_ata.to_account_info(),
+            &mut self.user_yes_ata.to_account_info(),
+            &mut self.global_no_ata.to_account_info(),
+            &mut self.user_no_ata.to_account_info(),
             &mut self.global_vault.to_account_info(),
             &self.user,
             amount,
             signer_seeds,
-            &mut self.user_info,
+            &self.token_program,
             &self.system_program,
-        )
+        )?;
+
+        if !self.user_info.is_initialized {
+            self.user_info.user = self.user.key();
+            self.user_info.is_initialized = true;
+        }
+        self.user_info.is_lp = self.lp_position.shares > 0;
+
+        Ok(())
     }
-}
\ No newline at end of file
+}
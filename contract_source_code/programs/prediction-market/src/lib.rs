@@ -22,8 +22,12 @@ pub mod utils;      // 工具函数
 
 // 导入指令模块
 use instructions::{
-    accept_authority::*, add_liquidity::*, configure::*, create_market::*, mint_no_token::*,
-    nominate_authority::*, resolution::*, swap::*, withdraw_liquidity::*,
+    accept_authority::*, accept_role::*, add_liquidity::*, add_to_whitelist::*, cancel_config::*,
+    claim_creator_fees::*, claim_lp_fees::*, configure::*, create_market::*, distribute_fees::*,
+    execute_config::*, init_fee_vault::*, migrate::*, migrate_config::*, nominate_authority::*,
+    nominate_role::*, propose_config::*, redeem::*, remove_from_whitelist::*, resolution::*,
+    set_global_pause::*, set_market_pause::*, settle_creator_bond::*, stake_lp::*, swap::*,
+    unstake_lp::*, withdraw_fees::*, withdraw_liquidity::*,
 };
 
 // 导入状态模块
@@ -40,8 +44,8 @@ pub mod prediction_market {
 
     /// 配置全局设置
     /// 
-    /// 由管理员调用，用于设置全局配置参数
-    /// 需要验证调用者是否为授权管理员
+    /// 由管理员调用，用于首次设置全局配置参数
+    /// 配置创建后的变更需通过 propose_config/execute_config 经时间锁生效
     /// 
     /// # 参数
     /// * `ctx` - 指令上下文
@@ -51,7 +55,80 @@ pub mod prediction_market {
     /// * `Result<()>` - 操作结果
     pub fn configure(ctx: Context<Configure>, new_config: Config) -> Result<()> {
         msg!("configure: {:#?}", new_config);
-        ctx.accounts.handler(new_config, ctx.bumps.config)
+        ctx.accounts
+            .handler(new_config, ctx.bumps.config, ctx.bumps.fee_vault)
+    }
+
+    /// 创建手续费金库
+    /// 
+    /// 供首次配置早于手续费金库引入的部署补建金库，交易与手续费分配均依赖该账户
+    /// 任何人均可调用，金库已存在时失败
+    /// 
+    /// # 参数
+    /// * `ctx` - 指令上下文
+    /// 
+    /// # 返回
+    /// * `Result<()>` - 操作结果
+    pub fn init_fee_vault(ctx: Context<InitFeeVault>) -> Result<()> {
+        ctx.accounts.process(ctx.bumps.fee_vault)
+    }
+
+    /// 升级旧版全局配置
+    /// 
+    /// 由旧配置中的管理员调用，将已部署的旧布局配置扩容并重写为当前布局（只能执行一次）
+    /// 旧配置中已有的字段原样沿用，新增字段取自传入的配置
+    /// 
+    /// # 参数
+    /// * `ctx` - 指令上下文
+    /// * `new_config` - 提供新增字段取值的配置
+    /// 
+    /// # 返回
+    /// * `Result<()>` - 操作结果
+    pub fn migrate_config(ctx: Context<MigrateConfig>, new_config: Config) -> Result<()> {
+        ctx.accounts.process(new_config)
+    }
+
+    /// 提交配置变更提案
+    /// 
+    /// 由管理员、手续费管理员或金库管理员调用
+    /// 手续费管理员只能修改费率，金库管理员只能修改手续费收款方
+    /// 提案需等待配置中的时间锁（slot数）到期后才能执行
+    /// 
+    /// # 参数
+    /// * `ctx` - 指令上下文
+    /// * `new_config` - 提案中的新配置
+    /// 
+    /// # 返回
+    /// * `Result<()>` - 操作结果
+    pub fn propose_config(ctx: Context<ProposeConfig>, new_config: Config) -> Result<()> {
+        msg!("propose_config: {:#?}", new_config);
+        ctx.accounts.process(new_config, ctx.bumps.pending_config)
+    }
+
+    /// 执行配置变更提案
+    /// 
+    /// 时间锁到期后任何人都可以调用，将待生效配置写入全局配置
+    /// 
+    /// # 参数
+    /// * `ctx` - 指令上下文
+    /// 
+    /// # 返回
+    /// * `Result<()>` - 操作结果
+    pub fn execute_config(ctx: Context<ExecuteConfig>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    /// 取消配置变更提案
+    /// 
+    /// 由管理员或提案发起者调用，撤销尚未执行的待生效配置
+    /// 
+    /// # 参数
+    /// * `ctx` - 指令上下文
+    /// 
+    /// # 返回
+    /// * `Result<()>` - 操作结果
+    pub fn cancel_config(ctx: Context<CancelConfig>) -> Result<()> {
+        ctx.accounts.process()
     }
 
     /// 提名新的管理员
@@ -83,30 +160,100 @@ pub mod prediction_market {
         ctx.accounts.process()
     }
 
-    /// 铸造NO代币
+    /// 提名角色的新持有者
     /// 
-    /// 为预测市场创建NO代币（表示"不同意"的代币）
-    /// 每个市场都需要一对YES和NO代币
+    /// 管理员或该角色的当前持有者可以将角色转移给其他账户
+    /// 这是一个两步过程，需要新持有者接受才能完成转移
     /// 
     /// # 参数
     /// * `ctx` - 指令上下文
-    /// * `no_symbol` - NO代币的符号
-    /// * `no_uri` - NO代币的元数据URI
+    /// * `role` - 要转移的角色
+    /// * `new_holder` - 新持有者的公钥
     /// 
     /// # 返回
     /// * `Result<()>` - 操作结果
-    pub fn mint_no_token(
-        ctx: Context<MintNoToken>,
-        no_symbol: String,
-        no_uri: String,
-    ) -> Result<()> {
-        ctx.accounts
-            .handler(no_symbol, no_uri, ctx.bumps.global_vault)
+    pub fn nominate_role(ctx: Context<NominateRole>, role: Role, new_holder: Pubkey) -> Result<()> {
+        ctx.accounts.process(role, new_holder)
+    }
+
+    /// 接受角色
+    /// 
+    /// 被提名者调用此函数来接受角色
+    /// 只有在被提名后才能调用此函数
+    /// 
+    /// # 参数
+    /// * `ctx` - 指令上下文
+    /// * `role` - 要接受的角色
+    /// 
+    /// # 返回
+    /// * `Result<()>` - 操作结果
+    pub fn accept_role(ctx: Context<AcceptRole>, role: Role) -> Result<()> {
+        ctx.accounts.process(role)
+    }
+
+    /// 将创建者加入白名单
+    /// 
+    /// 由白名单管理员调用，为创建者创建白名单账户
+    /// 配置开启 `whitelist_enabled` 后，只有白名单中的创建者才能创建市场
+    /// 
+    /// # 参数
+    /// * `ctx` - 指令上下文
+    /// * `creator` - 创建者的公钥
+    /// 
+    /// # 返回
+    /// * `Result<()>` - 操作结果
+    pub fn add_to_whitelist(ctx: Context<AddToWhitelist>, creator: Pubkey) -> Result<()> {
+        ctx.accounts.process(creator)
+    }
+
+    /// 将创建者移出白名单
+    /// 
+    /// 由白名单管理员调用，关闭创建者的白名单账户并退还租金
+    /// 
+    /// # 参数
+    /// * `ctx` - 指令上下文
+    /// * `creator` - 创建者的公钥
+    /// 
+    /// # 返回
+    /// * `Result<()>` - 操作结果
+    pub fn remove_from_whitelist(ctx: Context<RemoveFromWhitelist>, creator: Pubkey) -> Result<()> {
+        ctx.accounts.process(creator)
+    }
+
+    /// 切换全局暂停
+    /// 
+    /// 由暂停员调用，暂停后禁止创建市场、交易与增减流动性
+    /// 已结算市场的赎回不受影响
+    /// 
+    /// # 参数
+    /// * `ctx` - 指令上下文
+    /// * `paused` - 是否暂停
+    /// 
+    /// # 返回
+    /// * `Result<()>` - 操作结果
+    pub fn set_global_pause(ctx: Context<SetGlobalPause>, paused: bool) -> Result<()> {
+        ctx.accounts.process(paused)
+    }
+
+    /// 切换市场暂停
+    /// 
+    /// 由暂停员调用，暂停后禁止该市场的交易与增减流动性
+    /// 已结算市场的赎回不受影响
+    /// 
+    /// # 参数
+    /// * `ctx` - 指令上下文
+    /// * `paused` - 是否暂停
+    /// 
+    /// # 返回
+    /// * `Result<()>` - 操作结果
+    pub fn set_market_pause(ctx: Context<SetMarketPause>, paused: bool) -> Result<()> {
+        ctx.accounts.process(paused)
     }
 
     /// 创建预测市场
     /// 
-    /// 创建一个新的预测市场，包括YES代币的铸造
+    /// 在单条指令中原子地创建预测市场：YES/NO两个mint、金库ATA、元数据与市场账户
+    /// 同时创建记录市场问题与结算规则的 `MarketMetadata` 账户
     /// 市场创建者需要提供市场的基本信息
     /// 
     /// # 参数
@@ -116,7 +263,8 @@ pub mod prediction_market {
     /// # 返回
     /// * `Result<()>` - 操作结果
     pub fn create_market(ctx: Context<CreateMarket>, params: CreateMarketParams) -> Result<()> {
-        ctx.accounts.handler(params, ctx.bumps.global_vault)
+        ctx.accounts
+            .handler(params, ctx.bumps.global_vault, ctx.bumps.market_metadata)
     }
 
     /// 交易代币
@@ -149,16 +297,40 @@ pub mod prediction_market {
         )
     }
 
+    /// 毕业迁移
+    /// 
+    /// 某一侧联合曲线完成后，由管理员将该侧剩余代币与真实SOL
+    /// 经按市场派生的迁移托管，通过CPI注入配置中指定的外部AMM常数乘积池
+    /// 尚未退出的LP在该侧的流动性先划入LP托管额度，迁移后仍可按份额提取
+    /// 
+    /// # 参数
+    /// * `ctx` - 指令上下文（remaining_accounts 透传给AMM程序）
+    /// * `token_type` - 迁移的代币类型（0=YES，1=NO）
+    /// 
+    /// # 返回
+    /// * `Result<()>` - 操作结果
+    pub fn migrate<'info>(
+        ctx: Context<'_, '_, '_, 'info, Migrate<'info>>,
+        token_type: u8,
+    ) -> Result<()> {
+        ctx.accounts.handler(
+            token_type,
+            ctx.remaining_accounts,
+            ctx.bumps.global_vault,
+            ctx.bumps.migration_authority,
+        )
+    }
+
     /// 市场结算
     /// 
-    /// 由管理员调用，用于结算预测市场的结果
-    /// 根据实际结果分配奖励给持有正确代币的用户
+    /// 由结算员调用，用于结算预测市场的结果
+    /// 结算后持有者通过 redeem 赎回结果代币
     /// 
     /// # 参数
     /// * `ctx` - 指令上下文
     /// * `yes_amount` - YES代币的奖励数量
     /// * `no_amount` - NO代币的奖励数量
-    /// * `token_type` - 获胜的代币类型
+    /// * `token_type` - 结算结果（0=YES，1=NO，2=无效）
     /// * `is_completed` - 市场是否完成
     /// 
     /// # 返回
@@ -175,14 +347,32 @@ pub mod prediction_market {
             no_amount,
             token_type,
             is_completed,
-            ctx.bumps.global_vault,
         )
     }
 
+    /// 赎回结果代币
+    /// 
+    /// 市场结算后，持有者将结果代币转回全局金库并获得SOL，不收取手续费
+    /// 有获胜方时按获胜代币的兑付率支付；市场被判定无效时按该侧联合曲线的卖出价支付
+    /// 不受暂停开关影响，保证用户始终可以退出
+    /// 
+    /// # 参数
+    /// * `ctx` - 指令上下文
+    /// * `amount` - 赎回的代币数量
+    /// * `token_type` - 代币类型（0=YES，1=NO）
+    /// 
+    /// # 返回
+    /// * `Result<()>` - 操作结果
+    pub fn redeem(ctx: Context<Redeem>, amount: u64, token_type: u8) -> Result<()> {
+        ctx.accounts
+            .handler(amount, token_type, ctx.bumps.global_vault)
+    }
+
     /// 添加流动性
     /// 
     /// 用户可以向市场添加流动性，成为流动性提供者（LP）
-    /// LP可以获得交易手续费分成
+    /// 按注入比例获得该市场的LP份额代币，存放在托管中并记入用户的LP仓位（`LpPosition`）
+    /// 仓位中的份额按比例分得交易产生的LP手续费；市场结束（ending_slot/ending_timestamp）后不再接受注入
     /// 
     /// # 参数
     /// * `ctx` - 指令上下文
@@ -191,21 +381,126 @@ pub mod prediction_market {
     /// # 返回
     /// * `Result<()>` - 操作结果
     pub fn add_liquidity(ctx: Context<AddLiquidity>, amount: u64) -> Result<()> {
-        ctx.accounts.handler(amount)
+        ctx.accounts
+            .handler(amount, ctx.bumps.global_vault, ctx.bumps.lp_position)
     }
 
     /// 提取流动性
     /// 
-    /// 流动性提供者可以提取之前添加的流动性
-    /// 只能提取自己添加的流动性
+    /// 销毁LP仓位名下托管的份额，按其占总份额的比例提取流动性
+    /// 份额代币可转让，转让得到的份额经 stake_lp 存入仓位后即可赎回，不要求持有者自己注入过
+    /// 已累计的LP手续费结算进仓位，由 claim_lp_fees 领取
+    /// 按比例返还LP所持的SOL，交易形成的代币盈余以YES/NO代币返还
+    /// 市场结算后仍可提取：按获胜结果对LP剩余份额估值并以SOL返还
+    /// 
+    /// # 参数
+    /// * `ctx` - 指令上下文
+    /// * `shares` - 销毁的LP份额代币数量
+    /// 
+    /// # 返回
+    /// * `Result<()>` - 操作结果
+    pub fn withdraw_liquidity(ctx: Context<WithdrawLiquidity>, shares: u64) -> Result<()> {
+        ctx.accounts.handler(shares, ctx.bumps.global_vault)
+    }
+
+    /// 存入LP份额
+    /// 
+    /// 将钱包中的LP份额代币转入托管，记入调用者的LP仓位，此后参与LP手续费分配
+    /// 
+    /// # 参数
+    /// * `ctx` - 指令上下文
+    /// * `shares` - 存入的LP份额代币数量
+    /// 
+    /// # 返回
+    /// * `Result<()>` - 操作结果
+    pub fn stake_lp(ctx: Context<StakeLp>, shares: u64) -> Result<()> {
+        ctx.accounts.handler(shares, ctx.bumps.lp_position)
+    }
+
+    /// 取出LP份额
+    /// 
+    /// 将LP仓位中的份额代币转到调用者钱包，取出后可自由转让或用于其他协议
+    /// 钱包中的份额不参与LP手续费分配
+    /// 
+    /// # 参数
+    /// * `ctx` - 指令上下文
+    /// * `shares` - 取出的LP份额代币数量
+    /// 
+    /// # 返回
+    /// * `Result<()>` - 操作结果
+    pub fn unstake_lp(ctx: Context<UnstakeLp>, shares: u64) -> Result<()> {
+        ctx.accounts.handler(shares, ctx.bumps.global_vault)
+    }
+
+    /// 领取LP手续费
+    /// 
+    /// LP按仓位中的份额领取自上次结算以来累计的交易手续费
+    /// 不影响已提供的流动性
+    /// 
+    /// # 参数
+    /// * `ctx` - 指令上下文
+    /// 
+    /// # 返回
+    /// * `Result<()>` - 操作结果
+    pub fn claim_lp_fees(ctx: Context<ClaimLpFees>) -> Result<()> {
+        ctx.accounts.handler(ctx.bumps.global_vault)
+    }
+
+    /// 领取创建者手续费
+    /// 
+    /// 市场创建者领取该市场交易累计的创建者手续费
+    /// 
+    /// # 参数
+    /// * `ctx` - 指令上下文
+    /// 
+    /// # 返回
+    /// * `Result<()>` - 操作结果
+    pub fn claim_creator_fees(ctx: Context<ClaimCreatorFees>) -> Result<()> {
+        ctx.accounts.handler(ctx.bumps.global_vault)
+    }
+
+    /// 分配平台手续费
+    /// 
+    /// 任何人均可调用，将手续费金库中的平台手续费按配置权重分配给各收款方
+    /// 收款方账户通过 remaining_accounts 按配置顺序传入
+    /// 
+    /// # 参数
+    /// * `ctx` - 指令上下文
+    /// 
+    /// # 返回
+    /// * `Result<()>` - 操作结果
+    pub fn distribute_fees<'info>(
+        ctx: Context<'_, '_, '_, 'info, DistributeFees<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.handler(ctx.remaining_accounts)
+    }
+
+    /// 提取平台手续费
+    /// 
+    /// 由金库管理员调用，将手续费金库中划归金库管理员的手续费提取到团队钱包（上限为 `treasury_fees`）
+    /// 发出包含累计提取总额的 `WithdrawEvent`
+    /// 
+    /// # 参数
+    /// * `ctx` - 指令上下文
+    /// * `amount` - 提取数量（lamports）
+    /// 
+    /// # 返回
+    /// * `Result<()>` - 操作结果
+    pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
+        ctx.accounts.handler(amount)
+    }
+
+    /// 结算创建者保证金
+    /// 
+    /// 市场结算后任何人均可调用
+    /// 市场正常结算则返还创建者，被判定无效则罚没至手续费金库
     /// 
     /// # 参数
     /// * `ctx` - 指令上下文
-    /// * `amount` - 提取的SOL数量
     /// 
     /// # 返回
     /// * `Result<()>` - 操作结果
-    pub fn withdraw_liquidity(ctx: Context<WithdrawLiquidity>, amount: u64) -> Result<()> {
-        ctx.accounts.handler(amount, ctx.bumps.global_vault)
+    pub fn settle_creator_bond(ctx: Context<SettleCreatorBond>) -> Result<()> {
+        ctx.accounts.handler(ctx.bumps.bond_escrow)
     }
 }
@@ -3,6 +3,7 @@
 //! 定义预测市场合约的全局配置结构
 //! 包括管理员权限、手续费设置、代币配置等
 
+use crate::constants::{FEE_BASIS_POINTS, MAX_FEE_RECIPIENTS};
 use crate::errors::*;
 use anchor_lang::{prelude::*, AnchorDeserialize, AnchorSerialize};
 use core::fmt::Debug;
@@ -12,7 +13,7 @@ use core::fmt::Debug;
 /// 存储预测市场合约的全局配置参数
 /// 包括管理员权限、手续费设置、代币配置等
 #[account]
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct Config {
     /// 当前管理员公钥
     pub authority: Pubkey,
@@ -21,10 +22,37 @@ pub struct Config {
     /// 当前管理员提名新管理员后，新管理员需要调用accept_authority来确认
     pub pending_authority: Pubkey,
 
+    /// 手续费管理员：可通过 propose_config 调整费率字段
+    pub fee_admin: Pubkey,
+
+    /// 结算员：可结算市场
+    pub resolver: Pubkey,
+
+    /// 暂停员：可切换全局/市场暂停开关
+    pub pauser: Pubkey,
+
+    /// 白名单管理员：可增删白名单创建者
+    pub whitelist_manager: Pubkey,
+
+    /// 金库管理员：可从手续费金库提取划归其的平台手续费，并可通过 propose_config 调整手续费收款方
+    pub treasury: Pubkey,
+
+    /// 各角色待确认的新持有者（两步交接，见 nominate_role/accept_role）
+    pub pending_fee_admin: Pubkey,
+    pub pending_resolver: Pubkey,
+    pub pending_pauser: Pubkey,
+    pub pending_whitelist_manager: Pubkey,
+    pub pending_treasury: Pubkey,
+
     /// 团队钱包地址
-    /// 用于接收平台手续费
+    /// 用于接收市场创建费，以及金库管理员经 withdraw_fees 提取的手续费与罚没保证金
     pub team_wallet: Pubkey,
 
+    /// 平台手续费收款方及权重
+    /// 平台手续费先归集到手续费金库，再由 distribute_fees 按权重分配；
+    /// 团队钱包的份额留在金库中划归金库管理员，由 withdraw_fees 提取
+    pub fee_recipients: Vec<FeeRecipient>,
+
     /// 平台买入手续费（基点，如1000表示10%）
     pub platform_buy_fee: u64,
     
@@ -37,6 +65,21 @@ pub struct Config {
     /// 流动性提供者卖出手续费（基点）
     pub lp_sell_fee: u64,
 
+    /// 平台买入手续费的取值约束（基点）
+    pub platform_buy_fee_config: AmountConfig<u64>,
+
+    /// 平台卖出手续费的取值约束（基点）
+    pub platform_sell_fee_config: AmountConfig<u64>,
+
+    /// 创建者可选LP买入手续费的取值约束（基点）
+    pub lp_buy_fee_config: AmountConfig<u64>,
+
+    /// 创建者可选LP卖出手续费的取值约束（基点）
+    pub lp_sell_fee_config: AmountConfig<u64>,
+
+    /// 创建者手续费的取值约束（基点）
+    pub creator_fee_config: AmountConfig<u64>,
+
     /// 代币总供应量配置
     pub token_supply_config: u64,
     
@@ -46,11 +89,320 @@ pub struct Config {
     /// 初始真实代币储备配置
     pub initial_real_token_reserves_config: u64,
 
+    /// 初始虚拟SOL储备配置（联合曲线定价用）
+    pub initial_virtual_sol_reserves_config: u64,
+
+    /// 初始虚拟代币储备配置（联合曲线定价用，需大于初始真实代币储备）
+    pub initial_virtual_token_reserves_config: u64,
+
+    /// 联合曲线毕业阈值（lamports）
+    /// 某一侧买家净投入的SOL（yes_trade_sol/no_trade_sol）达到该值，或该侧代币售罄后，该侧曲线完成并停止交易。
+    /// 注意：阈值不按真实SOL储备判断——真实储备包含LP注入，否则仅靠加流动性即可触发毕业
+    pub curve_limit: u64,
+
     /// 最小SOL流动性要求
     pub min_sol_liquidity: u64,
 
+    /// 毕业迁移的目标AMM程序（常数乘积池）
+    pub amm_program: Pubkey,
+
+    /// 是否仅允许白名单中的创建者创建市场
+    pub whitelist_enabled: bool,
+
+    /// 市场创建费（lamports），创建市场时支付给团队钱包
+    pub market_creation_fee: u64,
+
+    /// 是否对白名单中的创建者免收市场创建费
+    pub whitelist_fee_waiver: bool,
+
+    /// 创建者保证金（lamports），创建市场时锁入该市场的托管PDA
+    /// 市场正常结算后返还创建者，判定无效则罚没至手续费金库并划归金库管理员
+    pub creator_bond: u64,
+
+    /// 配置变更的时间锁（slot数），propose_config 提交后需等待该时长才能 execute_config
+    pub config_timelock_slots: u64,
+
     /// 配置是否已初始化
     pub initialized: bool,
+
+    /// 全局暂停开关（暂停后禁止创建市场、交易与增减流动性，结算后的赎回不受影响）
+    pub paused: bool,
+}
+
+/// 管理角色
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Role {
+    /// 手续费管理员
+    FeeAdmin,
+    /// 结算员
+    Resolver,
+    /// 暂停员
+    Pauser,
+    /// 白名单管理员
+    WhitelistManager,
+    /// 金库管理员
+    Treasury,
+}
+
+impl Role {
+    /// 全部角色
+    pub const ALL: [Role; 5] = [
+        Role::FeeAdmin,
+        Role::Resolver,
+        Role::Pauser,
+        Role::WhitelistManager,
+        Role::Treasury,
+    ];
+}
+
+impl Config {
+    /// 指定角色的 (当前持有者, 待确认持有者)
+    pub fn role(&self, role: Role) -> (Pubkey, Pubkey) {
+        match role {
+            Role::FeeAdmin => (self.fee_admin, self.pending_fee_admin),
+            Role::Resolver => (self.resolver, self.pending_resolver),
+            Role::Pauser => (self.pauser, self.pending_pauser),
+            Role::WhitelistManager => (self.whitelist_manager, self.pending_whitelist_manager),
+            Role::Treasury => (self.treasury, self.pending_treasury),
+        }
+    }
+
+    /// 指定角色的 (当前持有者, 待确认持有者) 可变引用
+    pub fn role_mut(&mut self, role: Role) -> (&mut Pubkey, &mut Pubkey) {
+        match role {
+            Role::FeeAdmin => (&mut self.fee_admin, &mut self.pending_fee_admin),
+            Role::Resolver => (&mut self.resolver, &mut self.pending_resolver),
+            Role::Pauser => (&mut self.pauser, &mut self.pending_pauser),
+            Role::WhitelistManager => {
+                (&mut self.whitelist_manager, &mut self.pending_whitelist_manager)
+            }
+            Role::Treasury => (&mut self.treasury, &mut self.pending_treasury),
+        }
+    }
+
+    /// 从现有配置复制全部角色（角色只能通过两步交接变更）
+    pub fn copy_roles_from(&mut self, other: &Config) {
+        for role in Role::ALL {
+            let (holder, pending) = other.role(role);
+            let (holder_mut, pending_mut) = self.role_mut(role);
+            *holder_mut = holder;
+            *pending_mut = pending;
+        }
+    }
+
+    /// 校验配置参数（configure 与 propose_config 共用）
+    pub fn validate(&self) -> Result<()> {
+        // 基础参数校验：总供应与精度、初始储备的一致性
+        let decimal_multiplier = 10u64.pow(self.token_decimals_config as u32);
+        let fractional_tokens = self.token_supply_config % decimal_multiplier;
+        if fractional_tokens != 0 {
+            return Err(ValueInvalid.into());
+        }
+
+        require!(
+            self.token_supply_config
+                >= (self.initial_real_token_reserves_config / decimal_multiplier
+                    * self.initial_real_token_reserves_config),
+            PredictionMarketError::InvalidAmount
+        );
+
+        // 联合曲线参数校验：虚拟代币储备需覆盖真实储备，毕业阈值必须大于0
+        require!(
+            self.initial_virtual_sol_reserves_config > 0,
+            PredictionMarketError::InvalidParameter
+        );
+        require!(
+            self.initial_virtual_token_reserves_config > self.initial_real_token_reserves_config,
+            PredictionMarketError::InvalidParameter
+        );
+        require!(self.curve_limit > 0, PredictionMarketError::InvalidParameter);
+
+        // 平台费率需在约束范围内，默认LP费率需满足创建者可选范围
+        self.platform_buy_fee_config.validate(&self.platform_buy_fee)?;
+        self.platform_sell_fee_config.validate(&self.platform_sell_fee)?;
+        self.lp_buy_fee_config.validate(&self.lp_buy_fee)?;
+        self.lp_sell_fee_config.validate(&self.lp_sell_fee)?;
+        // 创建者未指定时按0收取，0需在允许范围内
+        self.creator_fee_config.validate(&0)?;
+
+        // 默认费率合计不能超过100%
+        for (platform_fee, lp_fee) in [
+            (self.platform_buy_fee, self.lp_buy_fee),
+            (self.platform_sell_fee, self.lp_sell_fee),
+        ] {
+            require!(
+                platform_fee.saturating_add(lp_fee) <= FEE_BASIS_POINTS,
+                PredictionMarketError::FeeTooHigh
+            );
+        }
+
+        // 手续费收款方：数量有上限，权重均大于0
+        require!(
+            self.fee_recipients.len() <= MAX_FEE_RECIPIENTS,
+            PredictionMarketError::InvalidParameter
+        );
+        require!(
+            self.fee_recipients.iter().all(|r| r.weight > 0),
+            PredictionMarketError::InvalidParameter
+        );
+
+        // 保证金托管为无数据的系统账户，非零保证金需满足免租
+        require!(
+            self.creator_bond == 0 || self.creator_bond >= Rent::get()?.minimum_balance(0),
+            PredictionMarketError::InvalidParameter
+        );
+
+        Ok(())
+    }
+
+    /// 从现有配置保留只能通过专用指令变更的字段（管理员、角色、暂停状态）
+    pub fn keep_managed_fields_from(&mut self, existing: &Config) {
+        self.authority = existing.authority;
+        self.pending_authority = existing.pending_authority;
+        self.copy_roles_from(existing);
+        self.paused = existing.paused;
+        self.initialized = existing.initialized;
+    }
+
+    /// 与现有配置相比，是否只修改了费率字段
+    ///
+    /// 平台费率的取值约束用于限制手续费管理员，只能由管理员修改
+    pub fn only_fees_changed(&self, existing: &Config) -> bool {
+        let mut expected = existing.clone();
+        expected.platform_buy_fee = self.platform_buy_fee;
+        expected.platform_sell_fee = self.platform_sell_fee;
+        expected.lp_buy_fee = self.lp_buy_fee;
+        expected.lp_sell_fee = self.lp_sell_fee;
+        expected.lp_buy_fee_config = self.lp_buy_fee_config.clone();
+        expected.lp_sell_fee_config = self.lp_sell_fee_config.clone();
+        expected.creator_fee_config = self.creator_fee_config.clone();
+        expected == *self
+    }
+
+    /// 与现有配置相比，是否只修改了手续费收款方
+    pub fn only_fee_recipients_changed(&self, existing: &Config) -> bool {
+        let mut expected = existing.clone();
+        expected.fee_recipients = self.fee_recipients.clone();
+        expected == *self
+    }
+
+    /// 提案发起者是否有权将现有配置变更为本配置
+    ///
+    /// 管理员可修改全部字段；手续费管理员只能修改费率；金库管理员只能修改手续费收款方
+    pub fn can_be_proposed_by(&self, existing: &Config, proposer: Pubkey) -> bool {
+        proposer == existing.authority
+            || (proposer == existing.fee_admin && self.only_fees_changed(existing))
+            || (proposer == existing.treasury && self.only_fee_recipients_changed(existing))
+    }
+
+    /// 序列化后的账户大小（含8字节判别符）
+    /// 序列化写入内存缓冲区不会失败，失败时按0计算并由后续写入报错
+    pub fn space(&self) -> usize {
+        8 + self.try_to_vec().map(|data| data.len()).unwrap_or_default()
+    }
+
+    /// 逐字段比较两份配置，返回发生变化的字段及新旧值
+    pub fn diff(&self, new: &Config) -> Vec<ConfigChange> {
+        let mut changes = Vec::new();
+        macro_rules! diff_fields {
+            ($($field:ident),+ $(,)?) => {
+                $(
+                    if self.$field != new.$field {
+                        changes.push(ConfigChange {
+                            field: stringify!($field).to_string(),
+                            old_value: format!("{:?}", self.$field),
+                            new_value: format!("{:?}", new.$field),
+                        });
+                    }
+                )+
+            };
+        }
+        diff_fields!(
+            authority,
+            pending_authority,
+            fee_admin,
+            resolver,
+            pauser,
+            whitelist_manager,
+            treasury,
+            pending_fee_admin,
+            pending_resolver,
+            pending_pauser,
+            pending_whitelist_manager,
+            pending_treasury,
+            team_wallet,
+            fee_recipients,
+            platform_buy_fee,
+            platform_sell_fee,
+            lp_buy_fee,
+            lp_sell_fee,
+            platform_buy_fee_config,
+            platform_sell_fee_config,
+            lp_buy_fee_config,
+            lp_sell_fee_config,
+            creator_fee_config,
+            token_supply_config,
+            token_decimals_config,
+            initial_real_token_reserves_config,
+            initial_virtual_sol_reserves_config,
+            initial_virtual_token_reserves_config,
+            curve_limit,
+            min_sol_liquidity,
+            amm_program,
+            whitelist_enabled,
+            market_creation_fee,
+            whitelist_fee_waiver,
+            creator_bond,
+            config_timelock_slots,
+            initialized,
+            paused,
+        );
+        changes
+    }
+}
+
+/// 旧版全局配置布局（角色拆分与时间锁引入之前已部署的账户）
+///
+/// 仅供 migrate_config 读取并升级到当前布局
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Debug)]
+pub struct LegacyConfig {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+    pub team_wallet: Pubkey,
+    pub platform_buy_fee: u64,
+    pub platform_sell_fee: u64,
+    pub lp_buy_fee: u64,
+    pub lp_sell_fee: u64,
+    pub token_supply_config: u64,
+    pub token_decimals_config: u8,
+    pub initial_real_token_reserves_config: u64,
+    pub min_sol_liquidity: u64,
+    pub initialized: bool,
+}
+
+impl LegacyConfig {
+    /// 旧版账户大小（含8字节判别符）；字段均为定长，升级后的账户必然更大
+    pub const SPACE: usize = 8 + LegacyConfig::INIT_SPACE;
+}
+
+/// 单个配置字段的变更（用于配置变更事件）
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct ConfigChange {
+    /// 字段名
+    pub field: String,
+    /// 变更前的值（Debug格式）
+    pub old_value: String,
+    /// 变更后的值（Debug格式）
+    pub new_value: String,
+}
+
+/// 平台手续费收款方
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct FeeRecipient {
+    /// 收款地址
+    pub wallet: Pubkey,
+    /// 分配权重（按各收款方权重之和计算占比）
+    pub weight: u16,
 }
 
 /// 数量配置枚举
@@ -107,3 +459,100 @@ impl<T: PartialEq + PartialOrd + Debug> AmountConfig<T> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn legacy_config_matches_deployed_layout() {
+        // 旧版 configure 按序列化长度创建账户：判别符 + 3个公钥 + 7个u64 + u8 + bool
+        assert_eq!(LegacyConfig::SPACE, 8 + 3 * 32 + 7 * 8 + 1 + 1);
+
+        let legacy = LegacyConfig {
+            authority: Pubkey::new_unique(),
+            pending_authority: Pubkey::default(),
+            team_wallet: Pubkey::new_unique(),
+            platform_buy_fee: 100,
+            platform_sell_fee: 100,
+            lp_buy_fee: 50,
+            lp_sell_fee: 50,
+            token_supply_config: 1_000_000_000_000_000,
+            token_decimals_config: 6,
+            initial_real_token_reserves_config: 793_100_000_000_000,
+            min_sol_liquidity: 5_000_000_000,
+            initialized: true,
+        };
+        assert_eq!(8 + legacy.try_to_vec().unwrap().len(), LegacyConfig::SPACE);
+    }
+
+    fn config() -> Config {
+        let any = || AmountConfig::Range { min: None, max: None };
+        Config {
+            authority: Pubkey::new_unique(),
+            pending_authority: Pubkey::default(),
+            fee_admin: Pubkey::new_unique(),
+            resolver: Pubkey::new_unique(),
+            pauser: Pubkey::new_unique(),
+            whitelist_manager: Pubkey::new_unique(),
+            treasury: Pubkey::new_unique(),
+            pending_fee_admin: Pubkey::default(),
+            pending_resolver: Pubkey::default(),
+            pending_pauser: Pubkey::default(),
+            pending_whitelist_manager: Pubkey::default(),
+            pending_treasury: Pubkey::default(),
+            team_wallet: Pubkey::new_unique(),
+            fee_recipients: vec![],
+            platform_buy_fee: 100,
+            platform_sell_fee: 100,
+            lp_buy_fee: 50,
+            lp_sell_fee: 50,
+            platform_buy_fee_config: AmountConfig::Range { min: None, max: Some(200) },
+            platform_sell_fee_config: AmountConfig::Range { min: None, max: Some(200) },
+            lp_buy_fee_config: any(),
+            lp_sell_fee_config: any(),
+            creator_fee_config: any(),
+            token_supply_config: 1_000_000_000,
+            token_decimals_config: 9,
+            initial_real_token_reserves_config: 800_000_000,
+            initial_virtual_sol_reserves_config: 30_000_000_000,
+            initial_virtual_token_reserves_config: 1_073_000_000,
+            curve_limit: 1_000_000_000,
+            min_sol_liquidity: 0,
+            amm_program: Pubkey::new_unique(),
+            whitelist_enabled: false,
+            market_creation_fee: 0,
+            whitelist_fee_waiver: false,
+            creator_bond: 0,
+            config_timelock_slots: 0,
+            initialized: true,
+            paused: false,
+        }
+    }
+
+    #[test]
+    fn roles_can_only_propose_their_own_fields() {
+        let existing = config();
+
+        let mut rates = existing.clone();
+        rates.platform_buy_fee = 150;
+        rates.lp_sell_fee_config = AmountConfig::Range { min: Some(10), max: None };
+        assert!(rates.can_be_proposed_by(&existing, existing.fee_admin));
+        assert!(!rates.can_be_proposed_by(&existing, existing.treasury));
+
+        let mut recipients = existing.clone();
+        recipients.fee_recipients = vec![FeeRecipient { wallet: Pubkey::new_unique(), weight: 1 }];
+        assert!(recipients.can_be_proposed_by(&existing, existing.treasury));
+        assert!(!recipients.can_be_proposed_by(&existing, existing.fee_admin));
+
+        // 平台费率约束与创建费只能由管理员修改
+        let mut bounds = existing.clone();
+        bounds.platform_buy_fee_config = AmountConfig::Range { min: None, max: Some(10_000) };
+        assert!(!bounds.can_be_proposed_by(&existing, existing.fee_admin));
+        let mut creation_fee = existing.clone();
+        creation_fee.market_creation_fee = 1;
+        assert!(!creation_fee.can_be_proposed_by(&existing, existing.fee_admin));
+        assert!(!creation_fee.can_be_proposed_by(&existing, existing.treasury));
+        assert!(creation_fee.can_be_proposed_by(&existing, existing.authority));
+    }
+}
@@ -0,0 +1,40 @@
+//! 手续费金库状态：平台手续费的独立归集账户（与 global_vault 中的用户资金隔离）
+
+use anchor_lang::prelude::*;
+
+/// 手续费金库账户：由程序持有，账户余额中超出免租部分与金库管理员份额之差即为待分配的平台手续费
+#[account]
+#[derive(InitSpace, Debug, Default)]
+pub struct FeeVault {
+    /// 已划归金库管理员、尚未提取的手续费（lamports），只能通过 withdraw_fees 提取
+    pub treasury_fees: u64,
+    /// 累计已分配给各收款方的手续费（lamports）
+    pub total_distributed: u64,
+    /// 累计被管理员提取的手续费（lamports）
+    pub total_withdrawn: u64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl FeeVault {
+    /// 手续费金库PDA种子前缀
+    pub const SEED_PREFIX: &'static str = "fee-vault";
+
+    /// 金库中的全部手续费（账户余额扣除免租部分）
+    pub fn available_fees(info: &AccountInfo) -> Result<u64> {
+        let rent_exempt = Rent::get()?.minimum_balance(info.data_len());
+        Ok(info.lamports().saturating_sub(rent_exempt))
+    }
+
+    /// 当前可按权重分配的手续费（扣除已划归金库管理员的部分）
+    pub fn distributable_fees(&self, info: &AccountInfo) -> Result<u64> {
+        Ok(Self::available_fees(info)?.saturating_sub(self.treasury_fees))
+    }
+
+    /// 从金库直接扣减lamports转给收款方（金库由本程序持有，无需系统程序CPI）
+    pub fn pay_out(info: &AccountInfo, recipient: &AccountInfo, amount: u64) -> Result<()> {
+        **info.try_borrow_mut_lamports()? -= amount;
+        **recipient.try_borrow_mut_lamports()? += amount;
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+//! LP仓位状态：按 (市场, LP) 派生的独立PDA，记录单个LP的注入、份额与手续费
+
+use anchor_lang::prelude::*;
+
+/// LP仓位账户：每个LP在每个市场中各有一个
+///
+/// 仓位名下的LP份额代币存放在全局金库的LP托管ATA中；
+/// 通过 unstake_lp 取出到钱包后可自由转让，持有者通过 stake_lp 存回自己的仓位后即可提取流动性
+#[account]
+#[derive(InitSpace, Debug, Default)]
+pub struct LpPosition {
+    /// 所属市场
+    pub market: Pubkey,
+    /// LP地址
+    pub lp: Pubkey,
+    /// 累计注入（净额）的SOL数量
+    pub deposit_amount: u64,
+    /// 托管在仓位名下的LP份额（手续费与提取均按此计算）
+    pub shares: u64,
+    /// 上次结算时的每份额累计LP手续费（检查点）
+    pub fee_checkpoint: u128,
+    /// 已结算但尚未领取的LP手续费（lamports）
+    pub pending_fees: u64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl LpPosition {
+    /// LP仓位PDA种子前缀
+    pub const SEED_PREFIX: &'static str = "lp-position";
+
+    /// 首次使用时写入仓位归属
+    pub fn init_if_empty(&mut self, market: Pubkey, lp: Pubkey, bump: u8) {
+        if self.lp == Pubkey::default() {
+            self.market = market;
+            self.lp = lp;
+            self.bump = bump;
+        }
+    }
+}
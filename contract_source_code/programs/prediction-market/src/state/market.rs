@@ -8,9 +8,18 @@
 
 // Top-level imports
 use crate::state::config::*;
+use crate::state::lp_position::LpPosition;
+use crate::constants::{
+    FEE_BASIS_POINTS, FEE_PER_SHARE_PRECISION, INVALID_OUTCOME, LP_SHARE_PRECISION,
+};
 use crate::errors::PredictionMarketError;
+use crate::events::{CompleteEvent, TradeEvent};
+use crate::utils::{
+    bps_mul, sol_transfer_from_user, sol_transfer_with_signer, token_transfer_user,
+    token_transfer_with_signer,
+};
 use anchor_lang::{prelude::*, AnchorDeserialize, AnchorSerialize};
-use anchor_spl::token::{Mint, Token};
+use anchor_spl::token::{Mint, Token, TokenAccount};
 
 // use anchor_spl::token::{self};
 
@@ -23,24 +32,20 @@ pub struct UserInfo {
     pub yes_balance: u64, // Amount of YES tokens purchased
     /// 持有的NO代币数量
     pub no_balance: u64,  // Amount of NO tokens purchased
-    /// 是否为流动性提供者
+    /// 是否为流动性提供者（仓位明细见 `LpPosition`）
     pub is_lp: bool,
     /// 是否已初始化
     pub is_initialized: bool,
 }
 
-/// 流动性提供者信息
-#[account]
-pub struct LpInfo {
-    /// 用户公钥
-    pub user: Pubkey,    // User's public key
-    /// 初始提供的SOL数量
-    pub sol_amount: u64, // Amount of init lp
-}
-
 /// 市场账户：存储预测市场的所有状态
+///
+/// 仅保存LP的汇总数据，单个LP的仓位见 `LpPosition`
 #[account]
+#[derive(InitSpace, Default)]
 pub struct Market {
+    /// 市场ID（sha256(question ‖ rules_hash)，YES/NO mint 的派生种子）
+    pub market_id: [u8; 32],
     /// YES代币铸造地址
     pub yes_token_mint: Pubkey,
     /// NO代币铸造地址
@@ -54,24 +59,734 @@ pub struct Market {
     pub real_yes_token_reserves: u64,
     pub real_yes_sol_reserves: u64,
     pub token_yes_total_supply: u64,
+    /// YES联合曲线虚拟储备（定价用，x*y=k）
+    pub virtual_yes_sol_reserves: u64,
+    pub virtual_yes_token_reserves: u64,
+    /// YES侧买家净投入的SOL（不含LP注入，毕业阈值按此判断）
+    pub yes_trade_sol: u64,
 
     /// NO代币相关储备
     pub initial_no_token_reserves: u64,
     pub real_no_token_reserves: u64,
     pub real_no_sol_reserves: u64,
     pub token_no_total_supply: u64,
+    /// NO联合曲线虚拟储备（定价用，x*y=k）
+    pub virtual_no_sol_reserves: u64,
+    pub virtual_no_token_reserves: u64,
+    /// NO侧买家净投入的SOL（不含LP注入，毕业阈值按此判断）
+    pub no_trade_sol: u64,
 
-    /// 市场状态
+    /// 市场状态（true 表示已结算）
     pub is_completed: bool,
+    /// 结算结果（0=YES，1=NO，2=无效；仅在 is_completed 时有效）
+    pub winning_token_type: u8,
+    /// YES/NO 联合曲线是否已完成（买家净投入SOL达到毕业阈值或代币售罄）
+    pub is_yes_curve_completed: bool,
+    pub is_no_curve_completed: bool,
+    /// YES/NO 流动性是否已迁移至外部AMM池
+    pub is_yes_migrated: bool,
+    pub is_no_migrated: bool,
     /// 开始槽位（可选）
     pub start_slot: Option<u64>,
     /// 结束槽位（可选）
     pub ending_slot: Option<u64>,
-
-    /// 流动性提供者列表
-    pub lps: Vec<LpInfo>,
+    /// 开始/结束交易时间（Unix时间戳，可选，可与槽位同时设置）
+    pub start_timestamp: Option<i64>,
+    pub ending_timestamp: Option<i64>,
+    /// 可结算时间（Unix时间戳，可选；未设置时以结束时间为准）
+    pub resolution_timestamp: Option<i64>,
+
+    /// LP在YES/NO两侧虚拟储备中的占比（定点，LP_SHARE_PRECISION = 100%）
+    /// 交易沿曲线移动储备时占比不变，只有注入/提取会改变
+    pub lp_yes_share: u64,
+    pub lp_no_share: u64,
+    /// LP注入时按价格补入的虚拟代币数量（无真实代币对应，提取时需扣除）
+    pub lp_yes_token_basis: u64,
+    pub lp_no_token_basis: u64,
     /// 总流动性数量
     pub total_lp_amount: u64,
+    /// 已发行的LP份额总量（与LP mint供应量一致）
+    pub total_lp_shares: u64,
+    /// 记在各LP仓位名下的份额总量（托管中的份额，仅这部分参与LP手续费分配）
+    pub staked_lp_shares: u64,
+    /// 每份额累计LP手续费（定点，FEE_PER_SHARE_PRECISION）
+    pub lp_fee_per_share: u128,
+    /// 留存在全局金库、尚未被领取的LP手续费（不计入曲线储备）
+    pub lp_fee_reserve: u64,
+    /// 迁移时从曲线中划出、留给LP按份额领取的SOL与YES/NO代币（不计入曲线储备）
+    pub lp_escrow_sol: u64,
+    pub lp_escrow_yes_tokens: u64,
+    pub lp_escrow_no_tokens: u64,
+
+    /// 创建时快照的手续费率（基点），之后的配置变更不影响本市场
+    pub platform_buy_fee: u64,
+    pub platform_sell_fee: u64,
+    pub lp_buy_fee: u64,
+    pub lp_sell_fee: u64,
+    /// 创建者手续费率（基点，买卖相同）
+    pub creator_fee: u64,
+    /// 留存在全局金库、尚未被创建者领取的手续费
+    pub creator_fee_reserve: u64,
+
+    /// 创建者锁入托管PDA的保证金（lamports）
+    pub creator_bond: u64,
+    /// 创建者保证金是否已结算（返还或罚没）
+    pub is_bond_settled: bool,
+
+    /// 市场暂停开关（暂停后禁止交易与增减流动性，结算后的赎回不受影响）
+    pub paused: bool,
+}
+
+impl Market {
+    /// 指定侧联合曲线是否已完成
+    pub fn is_curve_completed(&self, token_type: u8) -> bool {
+        if token_type == 0 {
+            self.is_yes_curve_completed
+        } else {
+            self.is_no_curve_completed
+        }
+    }
+
+    /// 指定侧的曲线储备：(虚拟SOL, 虚拟代币, 真实SOL, 真实代币)
+    pub fn curve_reserves(&self, token_type: u8) -> (u64, u64, u64, u64) {
+        if token_type == 0 {
+            (
+                self.virtual_yes_sol_reserves,
+                self.virtual_yes_token_reserves,
+                self.real_yes_sol_reserves,
+                self.real_yes_token_reserves,
+            )
+        } else {
+            (
+                self.virtual_no_sol_reserves,
+                self.virtual_no_token_reserves,
+                self.real_no_sol_reserves,
+                self.real_no_token_reserves,
+            )
+        }
+    }
+
+    /// 写回指定侧的曲线储备（参数顺序同 `curve_reserves`）
+    fn set_curve_reserves(
+        &mut self,
+        token_type: u8,
+        virtual_sol: u64,
+        virtual_token: u64,
+        real_sol: u64,
+        real_token: u64,
+    ) {
+        if token_type == 0 {
+            self.virtual_yes_sol_reserves = virtual_sol;
+            self.virtual_yes_token_reserves = virtual_token;
+            self.real_yes_sol_reserves = real_sol;
+            self.real_yes_token_reserves = real_token;
+        } else {
+            self.virtual_no_sol_reserves = virtual_sol;
+            self.virtual_no_token_reserves = virtual_token;
+            self.real_no_sol_reserves = real_sol;
+            self.real_no_token_reserves = real_token;
+        }
+    }
+
+    /// 指定侧买家净投入的SOL
+    pub fn trade_sol(&self, token_type: u8) -> u64 {
+        if token_type == 0 {
+            self.yes_trade_sol
+        } else {
+            self.no_trade_sol
+        }
+    }
+
+    /// 写回指定侧买家净投入的SOL
+    fn set_trade_sol(&mut self, token_type: u8, trade_sol: u64) {
+        if token_type == 0 {
+            self.yes_trade_sol = trade_sol;
+        } else {
+            self.no_trade_sol = trade_sol;
+        }
+    }
+
+    /// 指定侧流动性是否已迁移
+    pub fn is_migrated(&self, token_type: u8) -> bool {
+        if token_type == 0 {
+            self.is_yes_migrated
+        } else {
+            self.is_no_migrated
+        }
+    }
+
+    /// 迁移前将LP在该侧的全部流动性划入LP托管额度，返还数量与提取时的计算一致
+    ///
+    /// 之后该侧LP占比与基数归零，LP按份额从托管额度中领取，不再依赖该侧曲线储备
+    pub fn escrow_side_liquidity(&mut self, token_type: u8) -> Option<()> {
+        let total_lp_amount = self.total_lp_amount;
+        if total_lp_amount == 0 {
+            return Some(());
+        }
+
+        let (sol_out, token_out) =
+            self.remove_side_liquidity(token_type, total_lp_amount, total_lp_amount)?;
+        self.lp_escrow_sol = self.lp_escrow_sol.checked_add(sol_out)?;
+        if token_type == 0 {
+            self.lp_escrow_yes_tokens = self.lp_escrow_yes_tokens.checked_add(token_out)?;
+        } else {
+            self.lp_escrow_no_tokens = self.lp_escrow_no_tokens.checked_add(token_out)?;
+        }
+        Some(())
+    }
+
+    /// 从LP托管额度中按 `amount / total_amount` 的比例取出，返回 (SOL, YES代币, NO代币)
+    fn take_lp_escrow(&mut self, amount: u64, total_amount: u64) -> Option<(u64, u64, u64)> {
+        let pro_rata = |value: u64| -> Option<u64> {
+            (value as u128)
+                .checked_mul(amount as u128)?
+                .checked_div(total_amount as u128)?
+                .try_into()
+                .ok()
+        };
+        let sol = pro_rata(self.lp_escrow_sol)?;
+        let yes_tokens = pro_rata(self.lp_escrow_yes_tokens)?;
+        let no_tokens = pro_rata(self.lp_escrow_no_tokens)?;
+
+        self.lp_escrow_sol -= sol;
+        self.lp_escrow_yes_tokens -= yes_tokens;
+        self.lp_escrow_no_tokens -= no_tokens;
+        Some((sol, yes_tokens, no_tokens))
+    }
+
+    /// 迁移完成：清空该侧真实储备并标记已迁移
+    ///
+    /// 不调整LP占比与基数，调用方需先通过 `escrow_side_liquidity` 划出LP在该侧的流动性
+    pub fn mark_migrated(&mut self, token_type: u8) {
+        let (virtual_sol, virtual_token, _, _) = self.curve_reserves(token_type);
+        self.set_curve_reserves(token_type, virtual_sol, virtual_token, 0, 0);
+        if token_type == 0 {
+            self.is_yes_migrated = true;
+        } else {
+            self.is_no_migrated = true;
+        }
+    }
+
+    /// 按当前LP池子计算指定SOL数量对应的LP份额
+    ///
+    /// 首次注入（或池子为空）时按 1:1 发行
+    pub fn lp_shares_for_amount(&self, sol_amount: u64) -> Option<u64> {
+        if self.total_lp_shares == 0 || self.total_lp_amount == 0 {
+            return Some(sol_amount);
+        }
+
+        (sol_amount as u128)
+            .checked_mul(self.total_lp_shares as u128)?
+            .checked_div(self.total_lp_amount as u128)?
+            .try_into()
+            .ok()
+    }
+
+    /// 按当前LP池子计算指定LP份额可赎回的注入额度（向下取整）
+    pub fn lp_amount_for_shares(&self, shares: u64) -> Option<u64> {
+        if self.total_lp_shares == 0 {
+            return Some(0);
+        }
+
+        (shares as u128)
+            .checked_mul(self.total_lp_amount as u128)?
+            .checked_div(self.total_lp_shares as u128)?
+            .try_into()
+            .ok()
+    }
+
+    /// 指定侧的LP占比与虚拟代币基数
+    pub fn lp_position(&self, token_type: u8) -> (u64, u64) {
+        if token_type == 0 {
+            (self.lp_yes_share, self.lp_yes_token_basis)
+        } else {
+            (self.lp_no_share, self.lp_no_token_basis)
+        }
+    }
+
+    /// 写回指定侧的LP占比与虚拟代币基数
+    fn set_lp_position(&mut self, token_type: u8, share: u64, token_basis: u64) {
+        if token_type == 0 {
+            self.lp_yes_share = share;
+            self.lp_yes_token_basis = token_basis;
+        } else {
+            self.lp_no_share = share;
+            self.lp_no_token_basis = token_basis;
+        }
+    }
+
+    /// 向指定侧注入SOL流动性：虚拟SOL与虚拟代币同比例增加，价格不变
+    fn add_side_liquidity(&mut self, token_type: u8, sol_amount: u64) -> Option<()> {
+        if sol_amount == 0 {
+            return Some(());
+        }
+
+        let (virtual_sol, virtual_token, real_sol, real_token) = self.curve_reserves(token_type);
+        let (share, token_basis) = self.lp_position(token_type);
+
+        // 按当前价格补入的虚拟代币：Δt = Δs * vt / vs
+        let token_amount: u64 = (sol_amount as u128)
+            .checked_mul(virtual_token as u128)?
+            .checked_div(virtual_sol as u128)?
+            .try_into()
+            .ok()?;
+
+        // 注入前LP持有的SOL部分 + 本次注入，折算为新的占比
+        let lp_sol = (share as u128)
+            .checked_mul(virtual_sol as u128)?
+            .checked_div(LP_SHARE_PRECISION as u128)?;
+        let new_virtual_sol = virtual_sol.checked_add(sol_amount)?;
+        let new_share: u64 = lp_sol
+            .checked_add(sol_amount as u128)?
+            .checked_mul(LP_SHARE_PRECISION as u128)?
+            .checked_div(new_virtual_sol as u128)?
+            .try_into()
+            .ok()?;
+
+        self.set_curve_reserves(
+            token_type,
+            new_virtual_sol,
+            virtual_token.checked_add(token_amount)?,
+            real_sol.checked_add(sol_amount)?,
+            real_token,
+        );
+        self.set_lp_position(token_type, new_share, token_basis.checked_add(token_amount)?);
+
+        Some(())
+    }
+
+    /// 从指定侧按 `amount / total_amount` 的比例移除LP流动性
+    ///
+    /// 买方从曲线买走的代币会让LP持有的虚拟代币低于注入基数，这部分缺口按当前价格
+    /// 折算为SOL从LP应得中扣除并留在曲线中，保证剩余持有人仍能沿曲线卖回全部流通代币
+    /// 返回 (应返还的SOL, 应返还的代币盈余)
+    fn remove_side_liquidity(
+        &mut self,
+        token_type: u8,
+        amount: u64,
+        total_amount: u64,
+    ) -> Option<(u64, u64)> {
+        let (virtual_sol, virtual_token, real_sol, real_token) = self.curve_reserves(token_type);
+        let (share, token_basis) = self.lp_position(token_type);
+
+        // LP在该侧持有的SOL与代币（虚拟储备 × 占比）
+        let lp_sol = (share as u128)
+            .checked_mul(virtual_sol as u128)?
+            .checked_div(LP_SHARE_PRECISION as u128)?;
+        let lp_token = (share as u128)
+            .checked_mul(virtual_token as u128)?
+            .checked_div(LP_SHARE_PRECISION as u128)?;
+
+        let pro_rata = |value: u128| -> Option<u64> {
+            value
+                .checked_mul(amount as u128)?
+                .checked_div(total_amount as u128)?
+                .try_into()
+                .ok()
+        };
+        let sol_removed = pro_rata(lp_sol)?;
+        let token_removed = pro_rata(lp_token)?;
+        let basis_removed = pro_rata(token_basis as u128)?;
+        // 代币缺口按当前价格折算的SOL（向上取整），留在曲线中用于兑付卖方
+        let deficit = basis_removed.saturating_sub(token_removed);
+        let deficit_value: u64 = (deficit as u128)
+            .checked_mul(virtual_sol as u128)?
+            .checked_add((virtual_token as u128).checked_sub(1)?)?
+            .checked_div(virtual_token as u128)?
+            .try_into()
+            .ok()?;
+        let sol_out = sol_removed.saturating_sub(deficit_value).min(real_sol);
+        // 超出注入基数的部分是真实库存，以代币形式返还
+        let token_out = token_removed.saturating_sub(basis_removed).min(real_token);
+
+        // 被扣下的SOL不再属于LP，剩余占比只按LP未提取的部分计算
+        let new_virtual_sol = virtual_sol.checked_sub(sol_out)?;
+        let new_share: u64 = if new_virtual_sol == 0 {
+            0
+        } else {
+            lp_sol
+                .checked_sub(sol_removed as u128)?
+                .checked_mul(LP_SHARE_PRECISION as u128)?
+                .checked_div(new_virtual_sol as u128)?
+                .try_into()
+                .ok()?
+        };
+
+        self.set_curve_reserves(
+            token_type,
+            new_virtual_sol,
+            virtual_token.checked_sub(token_removed)?,
+            real_sol.checked_sub(sol_out)?,
+            real_token.checked_sub(token_out)?,
+        );
+        self.set_lp_position(token_type, new_share, token_basis.checked_sub(basis_removed)?);
+
+        Some((sol_out, token_out))
+    }
+
+    /// 按市场创建时快照的费率计算一笔交易的 (平台费, LP费, 创建者费)；没有仓位中的LP份额时不收取LP费
+    pub fn trade_fees(&self, direction: u8, amount: u64) -> Option<(u64, u64, u64)> {
+        let (platform_fee_bps, lp_fee_bps) = if direction == 0 {
+            (self.platform_buy_fee, self.lp_buy_fee)
+        } else {
+            (self.platform_sell_fee, self.lp_sell_fee)
+        };
+        let platform_fee = bps_mul(platform_fee_bps, amount, FEE_BASIS_POINTS)?;
+        let lp_fee = if self.staked_lp_shares == 0 {
+            0
+        } else {
+            bps_mul(lp_fee_bps, amount, FEE_BASIS_POINTS)?
+        };
+        let creator_fee = bps_mul(self.creator_fee, amount, FEE_BASIS_POINTS)?;
+        Some((platform_fee, lp_fee, creator_fee))
+    }
+
+    /// 将LP手续费按仓位中的份额计入每份额累加器
+    ///
+    /// 累加器向下取整，舍入部分留在 `lp_fee_reserve` 中，保证储备足以支付所有领取
+    pub fn accrue_lp_fees(&mut self, fee: u64) -> Option<()> {
+        if fee == 0 || self.staked_lp_shares == 0 {
+            return Some(());
+        }
+
+        let delta = (fee as u128)
+            .checked_mul(FEE_PER_SHARE_PRECISION)?
+            .checked_div(self.staked_lp_shares as u128)?;
+        self.lp_fee_per_share = self.lp_fee_per_share.checked_add(delta)?;
+        self.lp_fee_reserve = self.lp_fee_reserve.checked_add(fee)?;
+        Some(())
+    }
+
+    /// 结算LP仓位自上次检查点以来应得的手续费，并推进检查点
+    ///
+    /// 必须在仓位份额变化（注入/提取/存入/取出）之前调用
+    pub fn settle_lp_fees(&self, position: &mut LpPosition) -> Option<()> {
+        let earned: u64 = self
+            .lp_fee_per_share
+            .checked_sub(position.fee_checkpoint)?
+            .checked_mul(position.shares as u128)?
+            .checked_div(FEE_PER_SHARE_PRECISION)?
+            .try_into()
+            .ok()?;
+        position.pending_fees = position.pending_fees.checked_add(earned)?;
+        position.fee_checkpoint = self.lp_fee_per_share;
+        Some(())
+    }
+
+    /// 结算手续费后将份额记入LP仓位
+    pub fn credit_lp_position(&mut self, position: &mut LpPosition, shares: u64) -> Option<()> {
+        self.settle_lp_fees(position)?;
+        position.shares = position.shares.checked_add(shares)?;
+        self.staked_lp_shares = self.staked_lp_shares.checked_add(shares)?;
+        Some(())
+    }
+
+    /// 结算手续费后从LP仓位扣减份额
+    pub fn debit_lp_position(&mut self, position: &mut LpPosition, shares: u64) -> Option<()> {
+        self.settle_lp_fees(position)?;
+        position.shares = position.shares.checked_sub(shares)?;
+        self.staked_lp_shares = self.staked_lp_shares.checked_sub(shares)?;
+        Some(())
+    }
+
+    /// 将创建者手续费计入待领取余额
+    pub fn accrue_creator_fees(&mut self, fee: u64) -> Option<()> {
+        self.creator_fee_reserve = self.creator_fee_reserve.checked_add(fee)?;
+        Some(())
+    }
+
+    /// 结算后获胜代币的兑付池，返回 (可兑付SOL, 兑付分母, LP持有的获胜代币库存)
+    ///
+    /// 结算后该市场的全部真实SOL归获胜代币所有：流通中的获胜代币、LP持有的获胜代币库存
+    /// 与LP托管额度中的获胜代币按同一兑付率分配，失败代币价值为0。
+    /// `winning_vault_balance` 为全局金库中获胜代币的余额，用于推算流通量。
+    fn resolved_pool(&self, winning_vault_balance: u64) -> Option<(u64, u128, u64)> {
+        let winner = self.winning_token_type;
+        let (_, virtual_token, winner_sol, winner_token) = self.curve_reserves(winner);
+        let (_, _, loser_sol, _) = self.curve_reserves(1 - winner);
+        let (share, token_basis) = self.lp_position(winner);
+
+        // LP持有的获胜代币库存（超出注入基数的真实代币）
+        let lp_token: u64 = (share as u128)
+            .checked_mul(virtual_token as u128)?
+            .checked_div(LP_SHARE_PRECISION as u128)?
+            .try_into()
+            .ok()?;
+        let lp_inventory = lp_token.saturating_sub(token_basis).min(winner_token);
+
+        let (total_supply, escrow_tokens) = if winner == 0 {
+            (self.token_yes_total_supply, self.lp_escrow_yes_tokens)
+        } else {
+            (self.token_no_total_supply, self.lp_escrow_no_tokens)
+        };
+        let circulating = total_supply.saturating_sub(winning_vault_balance);
+
+        let pool_sol = winner_sol.checked_add(loser_sol)?;
+        let claimants = (circulating as u128)
+            .checked_add(lp_inventory as u128)?
+            .checked_add(escrow_tokens as u128)?;
+        Some((pool_sol, claimants, lp_inventory))
+    }
+
+    /// 从结算兑付池中扣减SOL：先从获胜侧扣减，不足部分由失败侧补足
+    fn take_resolved_sol(&mut self, sol: u64) -> Option<()> {
+        let winner = self.winning_token_type;
+        let loser = 1 - winner;
+        let (virtual_sol, virtual_token, winner_sol, winner_token) = self.curve_reserves(winner);
+        let (loser_virtual_sol, loser_virtual_token, loser_sol, loser_token) =
+            self.curve_reserves(loser);
+
+        let from_winner = sol.min(winner_sol);
+        let from_loser = sol.checked_sub(from_winner)?;
+        self.set_curve_reserves(
+            winner,
+            virtual_sol,
+            virtual_token,
+            winner_sol - from_winner,
+            winner_token,
+        );
+        self.set_curve_reserves(
+            loser,
+            loser_virtual_sol,
+            loser_virtual_token,
+            loser_sol.checked_sub(from_loser)?,
+            loser_token,
+        );
+        Some(())
+    }
+
+    /// 结算后按获胜结果赎回LP流动性（比例为 `amount / total_amount`），返还SOL数量
+    ///
+    /// LP库存与流通中的获胜代币按同一兑付率估值；若无人持有获胜代币，剩余SOL全部归LP。
+    /// 托管额度中的代币由调用方另行按份额返还，持有者再通过 redeem 按同一兑付率赎回。
+    fn remove_resolved_liquidity(
+        &mut self,
+        amount: u64,
+        total_amount: u64,
+        winning_vault_balance: u64,
+    ) -> Option<u64> {
+        let winner = self.winning_token_type;
+        let (pool_sol, claimants, lp_inventory) = self.resolved_pool(winning_vault_balance)?;
+        let lp_value = if claimants == 0 {
+            pool_sol as u128
+        } else {
+            (pool_sol as u128)
+                .checked_mul(lp_inventory as u128)?
+                .checked_div(claimants)?
+        };
+
+        let pro_rata = |value: u128| -> Option<u64> {
+            value
+                .checked_mul(amount as u128)?
+                .checked_div(total_amount as u128)?
+                .try_into()
+                .ok()
+        };
+        let sol_out = pro_rata(lp_value)?;
+        let inventory_out = pro_rata(lp_inventory as u128)?;
+
+        self.take_resolved_sol(sol_out)?;
+        let (virtual_sol, virtual_token, winner_sol, winner_token) = self.curve_reserves(winner);
+        self.set_curve_reserves(
+            winner,
+            virtual_sol,
+            virtual_token,
+            winner_sol,
+            winner_token.checked_sub(inventory_out)?,
+        );
+
+        // LP占比与基数同比例缩减，使剩余LP的库存按同一比例减少
+        let (share, token_basis) = self.lp_position(winner);
+        let remaining = total_amount.checked_sub(amount)?;
+        let scale = |value: u64| -> Option<u64> {
+            (value as u128)
+                .checked_mul(remaining as u128)?
+                .checked_div(total_amount as u128)?
+                .try_into()
+                .ok()
+        };
+        let new_share = scale(share)?;
+        let new_basis = scale(token_basis)?;
+        self.set_lp_position(winner, new_share, new_basis);
+
+        Some(sol_out)
+    }
+
+    /// 结算后赎回获胜代币，返还SOL数量（向下取整）
+    ///
+    /// 按 `resolved_pool` 的兑付率支付；`winning_vault_balance` 为赎回代币转入金库之前的余额
+    pub fn redeem_winning_tokens(&mut self, amount: u64, winning_vault_balance: u64) -> Option<u64> {
+        let (pool_sol, claimants, _) = self.resolved_pool(winning_vault_balance)?;
+        let sol_out: u64 = (pool_sol as u128)
+            .checked_mul(amount as u128)?
+            .checked_div(claimants)?
+            .try_into()
+            .ok()?;
+        self.take_resolved_sol(sol_out)?;
+        Some(sol_out)
+    }
+
+    /// 计算买入代币数量（虚拟储备常数乘积公式）
+    ///
+    /// token_out = vt * sol_in / (vs + sol_in)；超出真实代币储备时按售罄处理并反推所需SOL
+    pub fn get_tokens_for_buy_sol(&self, change_amount: u64, token_type: u8) -> Option<BuyResult> {
+        if change_amount == 0 {
+            return None;
+        }
+
+        let (virtual_sol, virtual_token, _, real_token) = self.curve_reserves(token_type);
+
+        let mut token_amount: u64 = (virtual_token as u128)
+            .checked_mul(change_amount as u128)?
+            .checked_div((virtual_sol as u128).checked_add(change_amount as u128)?)?
+            .try_into()
+            .ok()?;
+        let mut sol_amount = change_amount;
+
+        // 售罄：只卖出剩余真实代币，所需SOL向上取整
+        if token_amount > real_token {
+            token_amount = real_token;
+            let remaining = (virtual_token as u128).checked_sub(token_amount as u128)?;
+            sol_amount = (virtual_sol as u128)
+                .checked_mul(token_amount as u128)?
+                .checked_add(remaining.checked_sub(1)?)?
+                .checked_div(remaining)?
+                .try_into()
+                .ok()?;
+        }
+
+        if token_amount == 0 {
+            return None;
+        }
+
+        let new_reserves = virtual_token.checked_sub(token_amount)?;
+        let (current_yes_reserves, current_no_reserves) =
+            (self.virtual_yes_token_reserves, self.virtual_no_token_reserves);
+        let (new_yes_reserves, new_no_reserves) = if token_type == 0 {
+            (new_reserves, current_no_reserves)
+        } else {
+            (current_yes_reserves, new_reserves)
+        };
+
+        Some(BuyResult {
+            token_amount,
+            change_amount: sol_amount,
+            current_yes_reserves,
+            current_no_reserves,
+            new_yes_reserves,
+            new_no_reserves,
+        })
+    }
+
+    /// 应用买入操作（更新虚拟与真实储备）
+    pub fn apply_buy(&mut self, change_amount: u64, token_type: u8) -> Option<BuyResult> {
+        // 计算代币数量
+        let result = self.get_tokens_for_buy_sol(change_amount, token_type)?;
+
+        let (virtual_sol, _, real_sol, real_token) = self.curve_reserves(token_type);
+        let new_virtual_token = if token_type == 0 {
+            result.new_yes_reserves
+        } else {
+            result.new_no_reserves
+        };
+        self.set_curve_reserves(
+            token_type,
+            virtual_sol.checked_add(result.change_amount)?,
+            new_virtual_token,
+            real_sol.checked_add(result.change_amount)?,
+            real_token.checked_sub(result.token_amount)?,
+        );
+        let trade_sol = self.trade_sol(token_type).checked_add(result.change_amount)?;
+        self.set_trade_sol(token_type, trade_sol);
+
+        Some(result)
+    }
+
+    /// 应用卖出操作（更新虚拟与真实储备）
+    pub fn apply_sell(&mut self, change_amount: u64, token_type: u8) -> Option<SellResult> {
+        // 计算SOL数量
+        let result = self.get_tokens_for_sell_sol(change_amount, token_type)?;
+
+        let (virtual_sol, _, real_sol, real_token) = self.curve_reserves(token_type);
+        let new_virtual_token = if token_type == 0 {
+            result.new_yes_reserves
+        } else {
+            result.new_no_reserves
+        };
+        self.set_curve_reserves(
+            token_type,
+            virtual_sol.checked_sub(result.change_amount)?,
+            new_virtual_token,
+            real_sol.checked_sub(result.change_amount)?,
+            real_token.checked_add(result.token_amount)?,
+        );
+        // 卖方可能取走LP注入的SOL，净投入最低为0
+        let trade_sol = self.trade_sol(token_type).saturating_sub(result.change_amount);
+        self.set_trade_sol(token_type, trade_sol);
+
+        Some(result)
+    }
+
+    /// 计算卖出可得SOL数量（虚拟储备常数乘积公式）
+    ///
+    /// sol_out = vs * token_in / (vt + token_in)，且不超过该侧真实SOL储备
+    pub fn get_tokens_for_sell_sol(&self, token_amount: u64, token_type: u8) -> Option<SellResult> {
+        if token_amount == 0 {
+            return None;
+        }
+
+        let (virtual_sol, virtual_token, real_sol, _) = self.curve_reserves(token_type);
+
+        let change_amount: u64 = (virtual_sol as u128)
+            .checked_mul(token_amount as u128)?
+            .checked_div((virtual_token as u128).checked_add(token_amount as u128)?)?
+            .try_into()
+            .ok()?;
+
+        if change_amount == 0 || change_amount > real_sol {
+            return None;
+        }
+
+        let new_reserves = virtual_token.checked_add(token_amount)?;
+        let (current_yes_reserves, current_no_reserves) =
+            (self.virtual_yes_token_reserves, self.virtual_no_token_reserves);
+        let (new_yes_reserves, new_no_reserves) = if token_type == 0 {
+            (new_reserves, current_no_reserves)
+        } else {
+            (current_yes_reserves, new_reserves)
+        };
+
+        Some(SellResult {
+            token_amount,
+            change_amount,
+            current_yes_reserves,
+            current_no_reserves,
+            new_yes_reserves,
+            new_no_reserves,
+        })
+    }
+
+    /// 检查指定侧是否达到毕业条件（买家净投入SOL达到阈值或代币售罄），达到则标记完成
+    ///
+    /// LP注入的SOL同样计入真实储备，但不计入毕业阈值
+    /// 返回值表示本次调用是否触发了完成（用于发出 CompleteEvent）
+    pub fn try_complete_curve(&mut self, token_type: u8, curve_limit: u64) -> bool {
+        if self.is_curve_completed(token_type) {
+            return false;
+        }
+
+        let (_, _, _, real_token) = self.curve_reserves(token_type);
+        if self.trade_sol(token_type) < curve_limit && real_token > 0 {
+            return false;
+        }
+
+        if token_type == 0 {
+            self.is_yes_curve_completed = true;
+        } else {
+            self.is_no_curve_completed = true;
+        }
+        true
+    }
 }
 
 /// 卖出结果：包含价格计算和储备更新
@@ -79,15 +794,15 @@ pub struct Market {
 pub struct SellResult {
     /// 卖出的代币数量
     pub token_amount: u64,
-    /// 获得的SOL数量
+    /// 曲线付出的SOL数量（未扣手续费）
     pub change_amount: u64,
-    /// 当前YES储备
+    /// 当前YES虚拟代币储备
     pub current_yes_reserves: u64,
-    /// 当前NO储备
+    /// 当前NO虚拟代币储备
     pub current_no_reserves: u64,
-    /// 新的YES储备
+    /// 新的YES虚拟代币储备
     pub new_yes_reserves: u64,
-    /// 新的NO储备
+    /// 新的NO虚拟代币储备
     pub new_no_reserves: u64,
 }
 
@@ -96,30 +811,56 @@ pub struct SellResult {
 pub struct BuyResult {
     /// 买入的代币数量
     pub token_amount: u64,
-    /// 支付的SOL数量
+    /// 实际进入曲线的SOL数量（已扣手续费；售罄时可能小于输入）
     pub change_amount: u64,
-    /// 当前YES储备
+    /// 当前YES虚拟代币储备
     pub current_yes_reserves: u64,
-    /// 当前NO储备
+    /// 当前NO虚拟代币储备
     pub current_no_reserves: u64,
-    /// 新的YES储备
+    /// 新的YES虚拟代币储备
     pub new_yes_reserves: u64,
-    /// 新的NO储备
+    /// 新的NO虚拟代币储备
     pub new_no_reserves: u64,
 }
 
 /// 创建市场参数
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct CreateMarketParams {
+    /// 市场ID，必须等于 sha256(question ‖ rules_hash)，YES/NO mint 均由其派生
+    pub market_id: [u8; 32],
     /// YES代币符号
     pub yes_symbol: String,
     /// YES代币元数据URI
     pub yes_uri: String,
+    /// NO代币符号
+    pub no_symbol: String,
+    /// NO代币元数据URI
+    pub no_uri: String,
+
+    /// 市场问题
+    pub question: String,
+    /// 结算规则全文的哈希
+    pub rules_hash: [u8; 32],
+    /// 市场分类
+    pub category: String,
+    /// 结算数据来源说明
+    pub resolution_source: String,
+
+    /// 创建者选择的LP买入/卖出手续费（基点，可选；未设置时采用全局配置）
+    pub lp_buy_fee: Option<u64>,
+    pub lp_sell_fee: Option<u64>,
+    /// 创建者手续费（基点，可选；未设置时为0）
+    pub creator_fee: Option<u64>,
 
     /// 开始槽位（可选）
     pub start_slot: Option<u64>,
     /// 结束槽位（可选）
     pub ending_slot: Option<u64>,
+    /// 开始/结束交易时间（Unix时间戳，可选，可与槽位同时设置）
+    pub start_timestamp: Option<i64>,
+    pub ending_timestamp: Option<i64>,
+    /// 可结算时间（Unix时间戳，可选；未设置时以结束时间为准）
+    pub resolution_timestamp: Option<i64>,
 }
 
 /// 市场账户特征：定义市场相关的所有操作
@@ -146,7 +887,7 @@ pub trait MarketAccount<'info> {
         user_no_ata: &mut AccountInfo<'info>,
 
         source: &mut AccountInfo<'info>,
-        team_wallet: &mut AccountInfo<'info>,
+        fee_vault: &mut AccountInfo<'info>,
 
         amount: u64,
         direction: u8,
@@ -162,25 +903,8 @@ pub trait MarketAccount<'info> {
         system_program: &Program<'info, System>,
     ) -> Result<()>;
 
-    fn get_tokens_for_buy_sol(&self, change_amount: u64, token_type: u8) -> Option<BuyResult>;
-    fn get_tokens_for_sell_sol(&self, change_amount: u64, token_type: u8) -> Option<SellResult>;
-
-    fn apply_buy(&mut self, change_amount: u64, token_type: u8) -> Option<BuyResult>;
-    fn apply_sell(&mut self, change_amount: u64, token_type: u8) -> Option<SellResult>;
-
-    fn resolution(
-        &mut self,
-
-        source: &mut AccountInfo<'info>,
-
-        user: &mut AccountInfo<'info>,
-        signer: &[&[&[u8]]],
-        user_info_pda: &mut Account<'info, UserInfo>,
-
-        token_type: u8,
-
-        system_program: &Program<'info, System>,
-    ) -> Result<()>;
+    /// 结算后赎回结果代币，返回应付SOL
+    fn redeem(&mut self, amount: u64, token_type: u8, winning_vault_balance: u64) -> Result<u64>;
 
     fn add_liquidity(
         &mut self,
@@ -189,7 +913,6 @@ pub trait MarketAccount<'info> {
 
         user: &Signer<'info>,
         sol_amount: u64,
-        user_info_pda: &mut Account<'info, UserInfo>,
 
         system_program: &Program<'info, System>,
     ) -> Result<()>;
@@ -197,14 +920,20 @@ pub trait MarketAccount<'info> {
     fn withdraw_liquidity(
         &mut self,
 
+        global_yes_ata: &mut AccountInfo<'info>,
+        user_yes_ata: &mut AccountInfo<'info>,
+
+        global_no_ata: &mut AccountInfo<'info>,
+        user_no_ata: &mut AccountInfo<'info>,
+
         source: &mut AccountInfo<'info>,
 
         user: &Signer<'info>,
         sol_amount: u64,
 
         signer: &[&[&[u8]]],
-        user_info_pda: &mut Account<'info, UserInfo>,
 
+        token_program: &Program<'info, Token>,
         system_program: &Program<'info, System>,
     ) -> Result<()>;
 }
@@ -213,40 +942,64 @@ pub trait MarketAccount<'info> {
 /// 
 /// 面向Solidity工程师：
 /// - impl 类似于 Solidity 中的 contract 实现
-/// - 这里实现了简化的AMM逻辑（实际项目中需要更复杂的公式）
+/// - 这里实现了基于虚拟储备的联合曲线AMM逻辑
 /// - CPI调用类似于 Solidity 中的外部合约调用
 impl<'info> MarketAccount<'info> for Account<'info, Market> {
-    /// 交换实现：当前为占位，实际需要完整的AMM逻辑
+    /// 交换实现：基于虚拟储备的联合曲线（x*y=k）
+    ///
+    /// 买入：用户支付SOL（扣除平台费与LP费后进入曲线），从全局金库ATA获得代币
+    /// 平台费转入手续费金库，由 distribute_fees 按配置权重分配
+    /// 卖出：用户将代币转回全局金库ATA，从全局金库获得SOL（扣除平台费与LP费）
+    /// 费率取自市场创建时的快照，而非当前全局配置
+    /// LP费留在全局金库，按仓位中的份额累加到 `lp_fee_per_share`，由LP通过 claim_lp_fees 领取
+    /// 创建者费同样留在全局金库，计入 `creator_fee_reserve`，由创建者通过 claim_creator_fees 领取
     fn swap(
         &mut self,
-        _global_config: &Account<'info, Config>,
+        global_config: &Account<'info, Config>,
 
-        _yes_token_mint: &Account<'info, Mint>,
-        _global_yes_ata: &mut AccountInfo<'info>,
-        _user_yes_ata: &mut AccountInfo<'info>,
+        yes_token_mint: &Account<'info, Mint>,
+        global_yes_ata: &mut AccountInfo<'info>,
+        user_yes_ata: &mut AccountInfo<'info>,
 
-        _no_token_mint: &Account<'info, Mint>,
-        _global_no_ata: &mut AccountInfo<'info>,
-        _user_no_ata: &mut AccountInfo<'info>,
+        no_token_mint: &Account<'info, Mint>,
+        global_no_ata: &mut AccountInfo<'info>,
+        user_no_ata: &mut AccountInfo<'info>,
 
-        _source: &mut AccountInfo<'info>,
-        _team_wallet: &mut AccountInfo<'info>,
+        source: &mut AccountInfo<'info>,
+        fee_vault: &mut AccountInfo<'info>,
 
         amount: u64,
         direction: u8,
         token_type: u8,
         minimum_receive_amount: u64,
 
-        _user: &Signer<'info>,
-        _signer: &[&[&[u8]]],
+        user: &Signer<'info>,
+        signer: &[&[&[u8]]],
 
         user_info_pda: &mut Account<'info, UserInfo>,
 
-        _token_program: &Program<'info, Token>,
-        _system_program: &Program<'info, System>,
+        token_program: &Program<'info, Token>,
+        system_program: &Program<'info, System>,
     ) -> Result<()> {
-        if direction == 0 {
-            let Some(buy) = self.apply_buy(amount, token_type) else {
+        // 按代币类型选择对应的mint与ATA
+        let (token_mint, global_ata, user_ata) = if token_type == 0 {
+            (yes_token_mint, global_yes_ata, user_yes_ata)
+        } else {
+            (no_token_mint, global_no_ata, user_no_ata)
+        };
+
+        let (sol_amount, token_amount, fee_lamports, creator_fee_lamports) = if direction == 0 {
+            // 平台费、LP费与创建者费按输入金额计算，剩余部分进入曲线
+            let (platform_fee, lp_fee, creator_fee) = self
+                .trade_fees(direction, amount)
+                .ok_or(PredictionMarketError::ArithmeticError)?;
+            let sol_in = amount
+                .checked_sub(platform_fee)
+                .and_then(|v| v.checked_sub(lp_fee))
+                .and_then(|v| v.checked_sub(creator_fee))
+                .ok_or(PredictionMarketError::ArithmeticError)?;
+
+            let Some(buy) = self.apply_buy(sol_in, token_type) else {
                 return Err(error!(PredictionMarketError::ArithmeticError));
             };
 
@@ -254,6 +1007,41 @@ impl<'info> MarketAccount<'info> for Account<'info, Market> {
                 return Err(error!(PredictionMarketError::ReturnAmountTooSmall));
             }
 
+            // 代币售罄时只收取实际用量对应的手续费
+            let (platform_fee, lp_fee, creator_fee) = if buy.change_amount < sol_in {
+                self.trade_fees(direction, buy.change_amount)
+                    .ok_or(PredictionMarketError::ArithmeticError)?
+            } else {
+                (platform_fee, lp_fee, creator_fee)
+            };
+
+            // 进入曲线的SOL、LP费与创建者费一并转入全局金库，LP费计入累加器
+            let vault_amount = buy
+                .change_amount
+                .checked_add(lp_fee)
+                .and_then(|v| v.checked_add(creator_fee))
+                .ok_or(PredictionMarketError::OverflowOrUnderflowOccurred)?;
+            sol_transfer_from_user(user, source.clone(), system_program, vault_amount)?;
+            if platform_fee > 0 {
+                sol_transfer_from_user(user, fee_vault.clone(), system_program, platform_fee)?;
+            }
+            self.accrue_lp_fees(lp_fee)
+                .ok_or(PredictionMarketError::OverflowOrUnderflowOccurred)?;
+            self.accrue_creator_fees(creator_fee)
+                .ok_or(PredictionMarketError::OverflowOrUnderflowOccurred)?;
+            let fee = platform_fee
+                .checked_add(lp_fee)
+                .and_then(|v| v.checked_add(creator_fee))
+                .ok_or(PredictionMarketError::OverflowOrUnderflowOccurred)?;
+            token_transfer_with_signer(
+                global_ata.clone(),
+                source.clone(),
+                user_ata.clone(),
+                token_program,
+                signer,
+                buy.token_amount,
+            )?;
+
             if token_type == 0 {
                 user_info_pda.yes_balance = user_info_pda
                     .yes_balance
@@ -263,15 +1051,60 @@ impl<'info> MarketAccount<'info> for Account<'info, Market> {
                     .no_balance
                     .saturating_add(buy.token_amount);
             }
+
+            (buy.change_amount, buy.token_amount, fee, creator_fee)
         } else {
             let Some(sell) = self.apply_sell(amount, token_type) else {
                 return Err(error!(PredictionMarketError::ArithmeticError));
             };
 
-            if sell.change_amount < minimum_receive_amount {
+            let (platform_fee, lp_fee, creator_fee) = self
+                .trade_fees(direction, sell.change_amount)
+                .ok_or(PredictionMarketError::ArithmeticError)?;
+            let sol_out = sell
+                .change_amount
+                .checked_sub(platform_fee)
+                .and_then(|v| v.checked_sub(lp_fee))
+                .and_then(|v| v.checked_sub(creator_fee))
+                .ok_or(PredictionMarketError::ArithmeticError)?;
+
+            if sol_out < minimum_receive_amount {
                 return Err(error!(PredictionMarketError::ReturnAmountTooSmall));
             }
 
+            token_transfer_user(
+                user_ata.clone(),
+                user,
+                global_ata.clone(),
+                token_program,
+                sell.token_amount,
+            )?;
+            sol_transfer_with_signer(
+                source.clone(),
+                user.to_account_info(),
+                system_program,
+                signer,
+                sol_out,
+            )?;
+            if platform_fee > 0 {
+                sol_transfer_with_signer(
+                    source.clone(),
+                    fee_vault.clone(),
+                    system_program,
+                    signer,
+                    platform_fee,
+                )?;
+            }
+            // LP费与创建者费留在全局金库，分别计入累加器与创建者待领取余额
+            self.accrue_lp_fees(lp_fee)
+                .ok_or(PredictionMarketError::OverflowOrUnderflowOccurred)?;
+            self.accrue_creator_fees(creator_fee)
+                .ok_or(PredictionMarketError::OverflowOrUnderflowOccurred)?;
+            let fee = platform_fee
+                .checked_add(lp_fee)
+                .and_then(|v| v.checked_add(creator_fee))
+                .ok_or(PredictionMarketError::OverflowOrUnderflowOccurred)?;
+
             if token_type == 0 {
                 user_info_pda.yes_balance = user_info_pda
                     .yes_balance
@@ -281,159 +1114,258 @@ impl<'info> MarketAccount<'info> for Account<'info, Market> {
                     .no_balance
                     .saturating_sub(sell.token_amount);
             }
-        }
 
-        Ok(())
-    }
-
-    /// 计算买入代币数量（简化AMM公式）
-    fn get_tokens_for_buy_sol(&self, change_amount: u64, token_type: u8) -> Option<BuyResult> {
-        let current_yes_reserves = self.real_yes_sol_reserves;
-        let current_no_reserves = self.real_no_sol_reserves;
-        
-        // 根据代币类型计算代币数量
-        let token_amount = if token_type == 0 {
-            // 买入YES代币
-            let new_yes_reserves = current_yes_reserves + change_amount;
-            let new_no_reserves = current_no_reserves;
-            
-            // 简化计算 - 实际实现中需要使用正确的AMM公式
-            let token_amount = change_amount; // Simplified calculation
-            
-            BuyResult {
-                token_amount,
-                change_amount,
-                current_yes_reserves,
-                current_no_reserves,
-                new_yes_reserves,
-                new_no_reserves,
-            }
-        } else {
-            // 买入NO代币
-            let new_yes_reserves = current_yes_reserves;
-            let new_no_reserves = current_no_reserves + change_amount;
-            
-            // 简化计算 - 实际实现中需要使用正确的AMM公式
-            let token_amount = change_amount; // Simplified calculation
-            
-            BuyResult {
-                token_amount,
-                change_amount,
-                current_yes_reserves,
-                current_no_reserves,
-                new_yes_reserves,
-                new_no_reserves,
-            }
+            (sol_out, sell.token_amount, fee, creator_fee)
         };
-        
-        Some(token_amount)
-    }
-
-    /// 应用买入操作（更新储备）
-    fn apply_buy(&mut self, change_amount: u64, token_type: u8) -> Option<BuyResult> {
-        // 计算代币数量
-        let result = self.get_tokens_for_buy_sol(change_amount, token_type)?;
 
-        Some(result)
-    }
-
-    /// 应用卖出操作（更新储备）
-    fn apply_sell(&mut self, change_amount: u64, token_type: u8) -> Option<SellResult> {
-        // 计算SOL数量
-        let result = self.get_tokens_for_sell_sol(change_amount, token_type)?;
+        let timestamp = Clock::get()?.unix_timestamp;
+
+        emit!(TradeEvent {
+            user: user.key(),
+            token_yes: yes_token_mint.key(),
+            token_no: no_token_mint.key(),
+            market_info: self.key(),
+            sol_amount,
+            token_amount,
+            fee_lamports,
+            creator_fee_lamports,
+            is_buy: direction == 0,
+            is_yes_no: token_type == 0,
+            real_sol_reserves: self
+                .real_yes_sol_reserves
+                .saturating_add(self.real_no_sol_reserves),
+            real_token_yes_reserves: self.real_yes_token_reserves,
+            real_token_no_reserves: self.real_no_token_reserves,
+            timestamp,
+        });
+
+        // 买入后检查该侧曲线是否达到毕业阈值
+        if direction == 0 && self.try_complete_curve(token_type, global_config.curve_limit) {
+            let (virtual_sol, virtual_token, real_sol, real_token) =
+                self.curve_reserves(token_type);
+            emit!(CompleteEvent {
+                user: user.key(),
+                mint: token_mint.key(),
+                virtual_sol_reserves: virtual_sol,
+                virtual_token_reserves: virtual_token,
+                real_sol_reserves: real_sol,
+                real_token_reserves: real_token,
+                timestamp,
+            });
+        }
 
-        Some(result)
+        Ok(())
     }
 
-    /// 计算卖出SOL数量（简化AMM公式）
-    fn get_tokens_for_sell_sol(&self, token_amount: u64, token_type: u8) -> Option<SellResult> {
-        let current_yes_reserves = self.real_yes_sol_reserves;
-        let current_no_reserves = self.real_no_sol_reserves;
-        
-        // 根据代币类型计算SOL数量
-        let change_amount = if token_type == 0 {
-            // 卖出YES代币
-            let new_yes_reserves = current_yes_reserves.saturating_sub(token_amount);
-            let new_no_reserves = current_no_reserves;
-            
-            // 简化计算 - 实际实现中需要使用正确的AMM公式
-            let change_amount = token_amount; // Simplified calculation
-            
-            SellResult {
-                token_amount,
-                change_amount,
-                current_yes_reserves,
-                current_no_reserves,
-                new_yes_reserves,
-                new_no_reserves,
-            }
+    /// 赎回实现
+    ///
+    /// 用户将结果代币转回全局金库ATA，从全局金库获得SOL，不收取手续费：
+    /// 有获胜方时仅获胜代币可赎回，按结算兑付率支付；
+    /// 市场被判定无效时两侧代币均可赎回，按该侧联合曲线的当前卖出价支付
+    fn redeem(&mut self, amount: u64, token_type: u8, winning_vault_balance: u64) -> Result<u64> {
+        require!(self.is_completed, PredictionMarketError::MarketNotCompleted);
+        require!(amount > 0, PredictionMarketError::InvalidAmount);
+        require!(token_type <= 1, PredictionMarketError::InvalidArgument);
+
+        let sol_out = if self.winning_token_type == INVALID_OUTCOME {
+            let Some(sell) = self.apply_sell(amount, token_type) else {
+                return Err(error!(PredictionMarketError::ArithmeticError));
+            };
+            sell.change_amount
         } else {
-            // 卖出NO代币
-            let new_yes_reserves = current_yes_reserves;
-            let new_no_reserves = current_no_reserves.saturating_sub(token_amount);
-            
-            // 简化计算 - 实际实现中需要使用正确的AMM公式
-            let change_amount = token_amount; // Simplified calculation
-            
-            SellResult {
-                token_amount,
-                change_amount,
-                current_yes_reserves,
-                current_no_reserves,
-                new_yes_reserves,
-                new_no_reserves,
-            }
+            require!(
+                token_type == self.winning_token_type,
+                PredictionMarketError::NotWinningToken
+            );
+            let Some(sol_out) = self.redeem_winning_tokens(amount, winning_vault_balance) else {
+                return Err(error!(PredictionMarketError::ArithmeticError));
+            };
+            sol_out
         };
-        
-        Some(change_amount)
+        require!(sol_out > 0, PredictionMarketError::InsufficientSol);
+
+        Ok(sol_out)
     }
 
-    /// 市场结算实现（占位）
-    fn resolution(
+    /// 添加流动性实现
+    ///
+    /// SOL按两侧虚拟SOL储备的比例拆分注入，两侧价格均保持不变
+    fn add_liquidity(
         &mut self,
 
-        _source: &mut AccountInfo<'info>,
-
-        _user: &mut AccountInfo<'info>,
-        _signer: &[&[&[u8]]],
-        _user_info_pda: &mut Account<'info, UserInfo>,
+        source: &mut AccountInfo<'info>,
 
-        _token_type: u8,
+        user: &Signer<'info>,
+        sol_amount: u64,
 
-        _system_program: &Program<'info, System>,
+        system_program: &Program<'info, System>,
     ) -> Result<()> {
+        // 曲线毕业后不再接受注入
+        require!(
+            !self.is_yes_curve_completed && !self.is_no_curve_completed,
+            PredictionMarketError::CurveAlreadyCompleted
+        );
+
+        // 按虚拟SOL储备比例拆分到YES/NO两侧
+        let total_virtual_sol = (self.virtual_yes_sol_reserves as u128)
+            .checked_add(self.virtual_no_sol_reserves as u128)
+            .ok_or(PredictionMarketError::OverflowOrUnderflowOccurred)?;
+        let yes_amount: u64 = (sol_amount as u128)
+            .checked_mul(self.virtual_yes_sol_reserves as u128)
+            .and_then(|v| v.checked_div(total_virtual_sol))
+            .and_then(|v| v.try_into().ok())
+            .ok_or(PredictionMarketError::ArithmeticError)?;
+        let no_amount = sol_amount
+            .checked_sub(yes_amount)
+            .ok_or(PredictionMarketError::ArithmeticError)?;
+
+        self.add_side_liquidity(0, yes_amount)
+            .ok_or(PredictionMarketError::ArithmeticError)?;
+        self.add_side_liquidity(1, no_amount)
+            .ok_or(PredictionMarketError::ArithmeticError)?;
+
+        sol_transfer_from_user(user, source.clone(), system_program, sol_amount)?;
+
+        self.total_lp_amount = self
+            .total_lp_amount
+            .checked_add(sol_amount)
+            .ok_or(PredictionMarketError::OverflowOrUnderflowOccurred)?;
+
         Ok(())
     }
 
-    /// 添加流动性实现（占位）
-    fn add_liquidity(
+    /// 提取流动性实现
+    ///
+    /// `sol_amount` 为由销毁的LP份额折算的注入额度，据此计算在LP池中的占比；
+    /// 按占比返还两侧LP所持的SOL，交易造成的代币盈余（卖方卖入的库存）以YES/NO代币返还；
+    /// 市场结算后则按获胜结果对LP份额估值，全部以SOL返还；
+    /// 已迁移一侧在迁移时划入LP托管额度的SOL与代币按同一占比一并返还
+    fn withdraw_liquidity(
         &mut self,
 
-        _source: &mut AccountInfo<'info>,
+        global_yes_ata: &mut AccountInfo<'info>,
+        user_yes_ata: &mut AccountInfo<'info>,
+
+        global_no_ata: &mut AccountInfo<'info>,
+        user_no_ata: &mut AccountInfo<'info>,
 
-        _user: &Signer<'info>,
-        _sol_amount: u64,
+        source: &mut AccountInfo<'info>,
 
-        _user_info_pda: &mut Account<'info, UserInfo>,
+        user: &Signer<'info>,
+        sol_amount: u64,
+        signer: &[&[&[u8]]],
 
-        _system_program: &Program<'info, System>,
+        token_program: &Program<'info, Token>,
+        system_program: &Program<'info, System>,
     ) -> Result<()> {
-        Ok(())
-    }
+        require!(
+            sol_amount <= self.total_lp_amount,
+            PredictionMarketError::WITHDRAWLIQUIDITYSOLAMOUNTERROR
+        );
+
+        let total_lp_amount = self.total_lp_amount;
+        // 市场被判定无效时没有获胜方，LP按结算时的两侧储备取回流动性
+        let (sol_out, yes_tokens, no_tokens) = if self.is_completed
+            && self.winning_token_type != INVALID_OUTCOME
+        {
+            // 结算后：按获胜结果估值，LP份额部分仅返还SOL
+            let winning_ata = if self.winning_token_type == 0 {
+                &global_yes_ata
+            } else {
+                &global_no_ata
+            };
+            let winning_vault_balance =
+                TokenAccount::try_deserialize(&mut &winning_ata.try_borrow_data()?[..])?.amount;
 
-    /// 提取流动性实现（占位）
-    fn withdraw_liquidity(
-        &mut self,
+            let Some(sol_out) =
+                self.remove_resolved_liquidity(sol_amount, total_lp_amount, winning_vault_balance)
+            else {
+                return Err(error!(PredictionMarketError::ArithmeticError));
+            };
+            (sol_out, 0, 0)
+        } else {
+            let Some((yes_sol, yes_tokens)) =
+                self.remove_side_liquidity(0, sol_amount, total_lp_amount)
+            else {
+                return Err(error!(PredictionMarketError::ArithmeticError));
+            };
+            let Some((no_sol, no_tokens)) =
+                self.remove_side_liquidity(1, sol_amount, total_lp_amount)
+            else {
+                return Err(error!(PredictionMarketError::ArithmeticError));
+            };
+
+            let sol_out = yes_sol
+                .checked_add(no_sol)
+                .ok_or(PredictionMarketError::OverflowOrUnderflowOccurred)?;
+            (sol_out, yes_tokens, no_tokens)
+        };
+
+        // 托管额度在估值之后取出：结算估值时托管中的获胜代币仍需计入兑付分母
+        let Some((escrow_sol, escrow_yes_tokens, escrow_no_tokens)) =
+            self.take_lp_escrow(sol_amount, total_lp_amount)
+        else {
+            return Err(error!(PredictionMarketError::ArithmeticError));
+        };
+        let sol_out = sol_out
+            .checked_add(escrow_sol)
+            .ok_or(PredictionMarketError::OverflowOrUnderflowOccurred)?;
+        let yes_tokens = yes_tokens
+            .checked_add(escrow_yes_tokens)
+            .ok_or(PredictionMarketError::OverflowOrUnderflowOccurred)?;
+        let no_tokens = no_tokens
+            .checked_add(escrow_no_tokens)
+            .ok_or(PredictionMarketError::OverflowOrUnderflowOccurred)?;
+
+        // 全局金库必须保持免租
+        let rent_exempt = Rent::get()?.minimum_balance(0);
+        require!(
+            source.lamports().saturating_sub(sol_out) >= rent_exempt,
+            PredictionMarketError::InsufficientSol
+        );
+
+        if sol_out > 0 {
+            sol_transfer_with_signer(
+                source.clone(),
+                user.to_account_info(),
+                system_program,
+                signer,
+                sol_out,
+            )?;
+        }
+        if yes_tokens > 0 {
+            token_transfer_with_signer(
+                global_yes_ata.clone(),
+                source.clone(),
+                user_yes_ata.clone(),
+                token_program,
+                signer,
+                yes_tokens,
+            )?;
+        }
+        if no_tokens > 0 {
+            token_transfer_with_signer(
+                global_no_ata.clone(),
+                source.clone(),
+                user_no_ata.clone(),
+                token_program,
+                signer,
+                no_tokens,
+            )?;
+        }
 
-        _source: &mut AccountInfo<'info>,
+        self.total_lp_amount = self
+            .total_lp_amount
+            .checked_sub(sol_amount)
+            .ok_or(PredictionMarketError::OverflowOrUnderflowOccurred)?;
 
-        _user: &Signer<'info>,
-        _sol_amount: u64,
-        _signer: &[&[&[u8]]],
-        _user_info_pda: &mut Account<'info, UserInfo>,
+        msg!(
+            "WithdrawLiquidity: sol_out={}, yes_tokens={}, no_tokens={}",
+            sol_out,
+            yes_tokens,
+            no_tokens
+        );
 
-        _system_program: &Program<'info, System>,
-    ) -> Result<()> {
         Ok(())
     }
 }
@@ -490,3 +1422,349 @@ impl<'info> MarketAccount<'info> for Account<'info, Market> {
    - Anchor使用init约束和显式的空间分配
 */
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SUPPLY: u64 = 1_000_000_000_000_000;
+    const REAL_TOKEN: u64 = 793_100_000_000_000;
+    const VIRTUAL_TOKEN: u64 = 1_073_000_000_000_000;
+    const VIRTUAL_SOL: u64 = 30_000_000_000;
+    const SOL: u64 = 1_000_000_000;
+
+    fn new_market() -> Market {
+        Market {
+            initial_yes_token_reserves: SUPPLY,
+            real_yes_token_reserves: REAL_TOKEN,
+            token_yes_total_supply: SUPPLY,
+            virtual_yes_sol_reserves: VIRTUAL_SOL,
+            virtual_yes_token_reserves: VIRTUAL_TOKEN,
+            initial_no_token_reserves: SUPPLY,
+            real_no_token_reserves: REAL_TOKEN,
+            token_no_total_supply: SUPPLY,
+            virtual_no_sol_reserves: VIRTUAL_SOL,
+            virtual_no_token_reserves: VIRTUAL_TOKEN,
+            ..Default::default()
+        }
+    }
+
+    fn k(market: &Market, token_type: u8) -> u128 {
+        let (virtual_sol, virtual_token, _, _) = market.curve_reserves(token_type);
+        virtual_sol as u128 * virtual_token as u128
+    }
+
+    #[test]
+    fn buy_follows_constant_product() {
+        let mut market = new_market();
+        let k_before = k(&market, 0);
+
+        let buy = market.apply_buy(SOL, 0).unwrap();
+
+        let expected = (VIRTUAL_TOKEN as u128 * SOL as u128 / (VIRTUAL_SOL + SOL) as u128) as u64;
+        assert_eq!(buy.token_amount, expected);
+        assert_eq!(buy.change_amount, SOL);
+        assert_eq!(market.virtual_yes_sol_reserves, VIRTUAL_SOL + SOL);
+        assert_eq!(market.virtual_yes_token_reserves, VIRTUAL_TOKEN - expected);
+        assert_eq!(market.real_yes_sol_reserves, SOL);
+        assert_eq!(market.real_yes_token_reserves, REAL_TOKEN - expected);
+        assert_eq!(market.yes_trade_sol, SOL);
+        // 向下取整只会让 k 变大，不会让池子亏损
+        assert!(k(&market, 0) >= k_before);
+        // 另一侧不受影响
+        assert_eq!(market.virtual_no_sol_reserves, VIRTUAL_SOL);
+        assert_eq!(market.real_no_token_reserves, REAL_TOKEN);
+    }
+
+    #[test]
+    fn sell_after_buy_returns_no_more_sol() {
+        let mut market = new_market();
+        let buy = market.apply_buy(3 * SOL, 1).unwrap();
+        let k_before = k(&market, 1);
+
+        let sell = market.apply_sell(buy.token_amount, 1).unwrap();
+
+        assert!(sell.change_amount <= 3 * SOL);
+        assert!(k(&market, 1) >= k_before);
+        assert_eq!(market.real_no_sol_reserves, 3 * SOL - sell.change_amount);
+        assert_eq!(market.real_no_token_reserves, REAL_TOKEN);
+        assert_eq!(market.no_trade_sol, 3 * SOL - sell.change_amount);
+    }
+
+    #[test]
+    fn sell_is_capped_by_real_sol() {
+        let market = new_market();
+        // 尚无真实SOL时不能卖出
+        assert!(market.get_tokens_for_sell_sol(1_000_000, 0).is_none());
+        assert!(market.get_tokens_for_buy_sol(0, 0).is_none());
+    }
+
+    #[test]
+    fn sold_out_buy_rounds_required_sol_up() {
+        let mut market = new_market();
+        market.real_yes_token_reserves = 1_000_000_000;
+
+        let buy = market.get_tokens_for_buy_sol(10 * SOL, 0).unwrap();
+
+        assert_eq!(buy.token_amount, 1_000_000_000);
+        assert!(buy.change_amount < 10 * SOL);
+        // 支付 change_amount 至少能买到剩余代币，少付 1 lamport 则不够
+        let tokens_for = |sol: u64| {
+            (VIRTUAL_TOKEN as u128 * sol as u128 / (VIRTUAL_SOL as u128 + sol as u128)) as u64
+        };
+        assert!(tokens_for(buy.change_amount) >= buy.token_amount);
+        assert!(tokens_for(buy.change_amount - 1) < buy.token_amount);
+
+        market.apply_buy(10 * SOL, 0).unwrap();
+        assert_eq!(market.real_yes_token_reserves, 0);
+        assert_eq!(market.real_yes_sol_reserves, buy.change_amount);
+    }
+
+    #[test]
+    fn curve_completes_on_buyer_sol_only() {
+        let mut market = new_market();
+        let curve_limit = 5 * SOL;
+
+        // LP注入的SOL不计入毕业阈值
+        market.add_side_liquidity(0, 20 * SOL).unwrap();
+        assert_eq!(market.real_yes_sol_reserves, 20 * SOL);
+        assert!(!market.try_complete_curve(0, curve_limit));
+
+        market.apply_buy(4 * SOL, 0).unwrap();
+        assert!(!market.try_complete_curve(0, curve_limit));
+
+        market.apply_buy(SOL, 0).unwrap();
+        assert!(market.try_complete_curve(0, curve_limit));
+        assert!(market.is_yes_curve_completed);
+        assert!(!market.is_no_curve_completed);
+        // 已完成的曲线不会重复触发
+        assert!(!market.try_complete_curve(0, curve_limit));
+    }
+
+    #[test]
+    fn curve_completes_when_sold_out() {
+        let mut market = new_market();
+        market.real_no_token_reserves = 1_000_000_000;
+
+        market.apply_buy(SOL, 1).unwrap();
+
+        assert!(market.try_complete_curve(1, 100 * SOL));
+        assert!(market.is_no_curve_completed);
+    }
+
+    #[test]
+    fn lp_shares_redeem_pro_rata_and_round_down() {
+        let mut market = new_market();
+        assert_eq!(market.lp_shares_for_amount(3 * SOL), Some(3 * SOL));
+        market.total_lp_amount = 3 * SOL;
+        market.total_lp_shares = 3 * SOL;
+
+        // 份额不再与注入净额挂钩：池子因舍入多出的额度按份额分配
+        market.total_lp_amount = 3 * SOL + 2;
+        assert_eq!(market.lp_amount_for_shares(SOL), Some(SOL));
+        assert_eq!(market.lp_amount_for_shares(3 * SOL), Some(3 * SOL + 2));
+        assert_eq!(market.lp_shares_for_amount(SOL), Some(SOL - 1));
+
+        market.total_lp_shares = 0;
+        assert_eq!(market.lp_amount_for_shares(SOL), Some(0));
+    }
+
+    #[test]
+    fn lp_fees_accrue_per_position_share() {
+        let mut market = new_market();
+        market.accrue_lp_fees(SOL).unwrap();
+        assert_eq!(market.lp_fee_reserve, 0);
+
+        let mut alice = LpPosition::default();
+        let mut bob = LpPosition::default();
+        market.credit_lp_position(&mut alice, SOL).unwrap();
+        market.accrue_lp_fees(SOL).unwrap();
+
+        // 后加入的仓位不分享加入之前的手续费
+        market.credit_lp_position(&mut bob, 3 * SOL).unwrap();
+        market.accrue_lp_fees(4 * SOL).unwrap();
+
+        market.settle_lp_fees(&mut alice).unwrap();
+        market.settle_lp_fees(&mut bob).unwrap();
+        assert_eq!(alice.pending_fees, 2 * SOL);
+        assert_eq!(bob.pending_fees, 3 * SOL);
+
+        // 取出到钱包的份额不再参与分配，已结算的手续费保留在仓位中
+        market.debit_lp_position(&mut bob, 3 * SOL).unwrap();
+        market.accrue_lp_fees(SOL).unwrap();
+        market.settle_lp_fees(&mut alice).unwrap();
+        market.settle_lp_fees(&mut bob).unwrap();
+        assert_eq!(alice.pending_fees, 3 * SOL);
+        assert_eq!(bob.pending_fees, 3 * SOL);
+        assert_eq!(market.staked_lp_shares, SOL);
+        assert!(alice.pending_fees + bob.pending_fees <= market.lp_fee_reserve);
+    }
+
+    #[test]
+    fn side_liquidity_keeps_price_and_round_trips() {
+        let mut market = new_market();
+        let price_before = VIRTUAL_SOL as u128 * LP_SHARE_PRECISION as u128 / VIRTUAL_TOKEN as u128;
+
+        market.add_side_liquidity(0, 10 * SOL).unwrap();
+        let price_after = market.virtual_yes_sol_reserves as u128 * LP_SHARE_PRECISION as u128
+            / market.virtual_yes_token_reserves as u128;
+        assert!(price_before.abs_diff(price_after) <= 1);
+        assert_eq!(market.real_yes_token_reserves, REAL_TOKEN);
+
+        // 先提取一半，再提取剩余全部
+        let (sol_half, token_half) = market.remove_side_liquidity(0, 1, 2).unwrap();
+        assert!((5 * SOL - 1..=5 * SOL).contains(&sol_half));
+        assert_eq!(token_half, 0);
+
+        let (sol_rest, token_rest) = market.remove_side_liquidity(0, 1, 1).unwrap();
+        assert!(sol_half + sol_rest <= 10 * SOL);
+        assert!(sol_half + sol_rest >= 10 * SOL - 2);
+        assert_eq!(token_rest, 0);
+        assert_eq!(market.lp_yes_share, 0);
+        assert_eq!(market.real_yes_token_reserves, REAL_TOKEN);
+        assert!(market.real_yes_sol_reserves <= 2);
+    }
+
+    #[test]
+    fn side_liquidity_returns_token_surplus_after_sells() {
+        let mut market = new_market();
+        // LP注入前的买方在注入后卖出，卖入的代币库存归LP
+        let buy = market.apply_buy(2 * SOL, 0).unwrap();
+        market.add_side_liquidity(0, 10 * SOL).unwrap();
+        market.apply_sell(buy.token_amount, 0).unwrap();
+
+        let real_sol = market.real_yes_sol_reserves;
+        let real_token = market.real_yes_token_reserves;
+        let (sol_out, token_out) = market.remove_side_liquidity(0, 1, 1).unwrap();
+
+        assert!(token_out > 0);
+        assert!(sol_out < 10 * SOL);
+        assert!(sol_out <= real_sol);
+        assert!(token_out <= real_token);
+        assert_eq!(market.real_yes_sol_reserves, real_sol - sol_out);
+        assert_eq!(market.real_yes_token_reserves, real_token - token_out);
+        assert_eq!(market.lp_yes_share, 0);
+        assert_eq!(market.lp_yes_token_basis, 0);
+    }
+
+    #[test]
+    fn side_liquidity_exit_leaves_sol_for_buyers_to_sell() {
+        let mut market = new_market();
+        // LP注入后买方买入，LP随即全部退出，买方仍能全部卖回
+        market.add_side_liquidity(0, 30 * SOL).unwrap();
+        let buy = market.apply_buy(20 * SOL, 0).unwrap();
+
+        let (sol_out, token_out) = market.remove_side_liquidity(0, 1, 1).unwrap();
+        assert!(sol_out < 30 * SOL);
+        assert_eq!(token_out, 0);
+        assert_eq!(market.lp_yes_share, 0);
+
+        assert!(market.apply_sell(buy.token_amount, 0).is_some());
+        assert_eq!(market.real_yes_token_reserves, REAL_TOKEN);
+    }
+
+    #[test]
+    fn migration_escrows_lp_liquidity_for_later_withdrawal() {
+        let mut market = new_market();
+        market.add_side_liquidity(0, 4 * SOL).unwrap();
+        market.total_lp_amount = 4 * SOL;
+        let buy = market.apply_buy(10 * SOL, 0).unwrap();
+        market.apply_sell(buy.token_amount / 2, 0).unwrap();
+
+        // 划入托管额度的数量与LP当场全部提取时一致
+        let mut expected = market.clone();
+        let (sol_out, token_out) = expected.remove_side_liquidity(0, 1, 1).unwrap();
+        market.escrow_side_liquidity(0).unwrap();
+        assert_eq!(market.lp_escrow_sol, sol_out);
+        assert_eq!(market.lp_escrow_yes_tokens, token_out);
+        assert_eq!(market.lp_yes_share, 0);
+        assert_eq!(market.lp_yes_token_basis, 0);
+        assert_eq!(market.real_yes_sol_reserves, expected.real_yes_sol_reserves);
+
+        // 迁移后LP按份额分批领取，合计不超过托管额度
+        market.mark_migrated(0);
+        let first = market.take_lp_escrow(SOL, 4 * SOL).unwrap();
+        let rest = market.take_lp_escrow(3 * SOL, 3 * SOL).unwrap();
+        assert_eq!(first.0 + rest.0, sol_out);
+        assert_eq!(first.1 + rest.1, token_out);
+        assert_eq!(market.lp_escrow_sol, 0);
+        assert_eq!(market.lp_escrow_yes_tokens, 0);
+    }
+
+    #[test]
+    fn resolved_liquidity_takes_whole_pool_without_holders() {
+        let mut market = new_market();
+        market.add_side_liquidity(0, 4 * SOL).unwrap();
+        market.add_side_liquidity(1, 6 * SOL).unwrap();
+        market.is_completed = true;
+        market.winning_token_type = 0;
+
+        // 获胜代币全部在金库中：无外部持有人，LP取得全部SOL
+        let half = market.remove_resolved_liquidity(1, 2, SUPPLY).unwrap();
+        assert_eq!(half, 5 * SOL);
+        let rest = market.remove_resolved_liquidity(1, 1, SUPPLY).unwrap();
+        assert_eq!(rest, 5 * SOL);
+        assert_eq!(market.real_yes_sol_reserves + market.real_no_sol_reserves, 0);
+        assert_eq!(market.lp_yes_share, 0);
+    }
+
+    #[test]
+    fn resolved_liquidity_leaves_enough_for_winning_holders() {
+        let mut market = new_market();
+        // 买方在LP注入前买入YES、注入后卖回一半，卖入的YES成为LP库存
+        let buy = market.apply_buy(3 * SOL, 0).unwrap();
+        market.add_side_liquidity(0, 5 * SOL).unwrap();
+        market.add_side_liquidity(1, 5 * SOL).unwrap();
+        market.apply_sell(buy.token_amount / 2, 0).unwrap();
+        market.apply_buy(2 * SOL, 1).unwrap();
+        market.is_completed = true;
+        market.winning_token_type = 0;
+
+        let circulating = buy.token_amount - buy.token_amount / 2;
+        let vault_balance = SUPPLY - circulating;
+        let pool_before = market.real_yes_sol_reserves + market.real_no_sol_reserves;
+        let lp_token = market.lp_yes_share as u128 * market.virtual_yes_token_reserves as u128
+            / LP_SHARE_PRECISION as u128;
+        let lp_inventory = lp_token as u64 - market.lp_yes_token_basis;
+        assert!(lp_inventory > 0);
+
+        let sol_out = market.remove_resolved_liquidity(1, 1, vault_balance).unwrap();
+
+        let pool_after = market.real_yes_sol_reserves + market.real_no_sol_reserves;
+        assert!(sol_out > 0);
+        assert_eq!(pool_before - pool_after, sol_out);
+        // 剩余SOL足够按同一兑付率赎回流通中的获胜代币
+        let claimants = circulating as u128 + lp_inventory as u128;
+        assert!(pool_after as u128 * claimants >= pool_before as u128 * circulating as u128);
+    }
+
+    #[test]
+    fn winning_holders_and_lp_redeem_without_overdrawing_the_pool() {
+        let mut market = new_market();
+        let buy = market.apply_buy(3 * SOL, 0).unwrap();
+        market.add_side_liquidity(0, 5 * SOL).unwrap();
+        market.add_side_liquidity(1, 5 * SOL).unwrap();
+        market.apply_sell(buy.token_amount / 2, 0).unwrap();
+        market.apply_buy(2 * SOL, 1).unwrap();
+        market.is_completed = true;
+        market.winning_token_type = 0;
+
+        // 持有者分两次赎回，LP随后退出，最后一位持有者赎回：每一步都不超出兑付池
+        let circulating = buy.token_amount - buy.token_amount / 2;
+        let mut vault_balance = SUPPLY - circulating;
+        let pool_before = market.real_yes_sol_reserves + market.real_no_sol_reserves;
+        let mut paid = 0;
+        for amount in [circulating / 3, circulating / 3] {
+            paid += market.redeem_winning_tokens(amount, vault_balance).unwrap();
+            vault_balance += amount;
+        }
+        paid += market.remove_resolved_liquidity(1, 1, vault_balance).unwrap();
+        let last = circulating - 2 * (circulating / 3);
+        paid += market.redeem_winning_tokens(last, vault_balance).unwrap();
+
+        let pool_after = market.real_yes_sol_reserves + market.real_no_sol_reserves;
+        assert_eq!(pool_before - pool_after, paid);
+        assert!(paid <= pool_before);
+        assert_eq!(market.lp_yes_share, 0);
+    }
+}
@@ -0,0 +1,61 @@
+//! 市场元数据状态：链上记录市场问题与结算规则（创建后不可修改）
+
+use anchor_lang::prelude::*;
+// anchor_lang 未导出哈希模块，借用 anchor_spl 依赖链中的 solana_program
+use anchor_spl::associated_token::spl_associated_token_account::solana_program::hash::hashv;
+
+/// 市场元数据账户：与市场账户一同创建，每个市场一个
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct MarketMetadata {
+    /// 所属市场
+    pub market: Pubkey,
+    /// 市场问题
+    #[max_len(256)]
+    pub question: String,
+    /// 结算规则全文的哈希（规则全文存放在链下）
+    pub rules_hash: [u8; 32],
+    /// 市场分类
+    #[max_len(32)]
+    pub category: String,
+    /// 结算数据来源说明
+    #[max_len(128)]
+    pub resolution_source: String,
+    /// 创建时间（Unix时间戳）
+    pub created_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl MarketMetadata {
+    /// 市场元数据PDA种子前缀
+    pub const SEED_PREFIX: &'static str = "market-metadata";
+
+    /// 各文本字段的最大字节长度（与 `max_len` 一致）
+    pub const MAX_QUESTION_LEN: usize = 256;
+    pub const MAX_CATEGORY_LEN: usize = 32;
+    pub const MAX_RESOLUTION_SOURCE_LEN: usize = 128;
+
+    /// 由问题与规则哈希确定的市场ID：sha256(question ‖ rules_hash)
+    ///
+    /// 同一问题与规则只能创建一个市场，YES/NO mint 地址也随之确定
+    pub fn market_id(question: &str, rules_hash: &[u8; 32]) -> [u8; 32] {
+        hashv(&[question.as_bytes(), rules_hash]).to_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn market_id_is_sha256_of_question_and_rules_hash() {
+        // 与客户端 computeMarketId 的拼接方式一致
+        let expected = [
+            219, 104, 245, 121, 109, 22, 97, 244, 107, 69, 109, 13, 12, 139, 111, 123, 40, 230,
+            186, 49, 46, 175, 141, 201, 180, 189, 197, 123, 25, 33, 195, 13,
+        ];
+        assert_eq!(MarketMetadata::market_id("Will it rain?", &[7; 32]), expected);
+        assert_ne!(MarketMetadata::market_id("Will it rain?", &[8; 32]), expected);
+    }
+}
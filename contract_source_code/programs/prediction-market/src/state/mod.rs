@@ -1,6 +1,10 @@
 //! 状态模块：定义所有账户结构
 
 pub mod config;    // 全局配置状态
+pub mod fee_vault; // 手续费金库状态
 pub mod global;    // 全局平台状态
+pub mod lp_position; // LP仓位状态
 pub mod market;    // 市场状态与逻辑
+pub mod market_metadata; // 市场元数据状态
+pub mod pending_config; // 待生效配置状态
 pub mod whitelist; // 白名单状态
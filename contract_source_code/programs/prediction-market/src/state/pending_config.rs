@@ -0,0 +1,28 @@
+//! 待生效配置状态：propose_config 提交的配置变更，时间锁到期后由 execute_config 生效
+
+use crate::state::config::Config;
+use anchor_lang::prelude::*;
+
+/// 待生效配置账户：全局唯一，同一时间只能存在一个配置提案
+#[account]
+#[derive(Debug)]
+pub struct PendingConfig {
+    /// 提案发起者（管理员、手续费管理员或金库管理员）
+    pub proposer: Pubkey,
+    /// 最早可执行的slot
+    pub eta_slot: u64,
+    /// PDA bump
+    pub bump: u8,
+    /// 提案中的新配置
+    pub config: Config,
+}
+
+impl PendingConfig {
+    /// 待生效配置PDA种子前缀
+    pub const SEED_PREFIX: &'static str = "pending-config";
+
+    /// 按提案配置计算账户大小（含8字节判别符）
+    pub fn space(config: &Config) -> usize {
+        32 + 8 + 1 + config.space()
+    }
+}
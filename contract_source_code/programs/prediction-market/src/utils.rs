@@ -192,6 +192,7 @@ pub fn sol_transfer_with_signer<'info>(
 /// 销毁的代币将从总供应量中永久移除
 /// 
 /// # 参数
+/// * `mint` - 代币铸造账户
 /// * `from` - 要销毁代币的账户
 /// * `authority` - PDA授权账户
 /// * `token_program` - SPL代币程序
@@ -201,6 +202,7 @@ pub fn sol_transfer_with_signer<'info>(
 /// # 返回
 /// * `Result<()>` - 操作结果
 pub fn token_burn_with_signer<'info>(
+    mint: AccountInfo<'info>, // 代币铸造账户
     from: AccountInfo<'info>, // 要销毁代币的账户
     authority: AccountInfo<'info>, // PDA授权账户
     token_program: &Program<'info, Token>, // SPL代币程序
@@ -211,7 +213,7 @@ pub fn token_burn_with_signer<'info>(
     let cpi_ctx: CpiContext<_> = CpiContext::new_with_signer(
         token_program.to_account_info(),
         token::Burn {
-            mint: from.to_account_info(), // 代币铸造账户
+            mint, // 代币铸造账户
             from, // 要销毁的账户
             authority, // PDA授权账户
         },
@@ -223,6 +225,79 @@ pub fn token_burn_with_signer<'info>(
     Ok(())
 }
 
+/// 使用PDA铸造代币
+/// 
+/// 使用PDA作为mint authority向目标账户铸造代币
+/// 
+/// # 参数
+/// * `mint` - 代币铸造账户
+/// * `to` - 目标代币账户
+/// * `authority` - PDA授权账户
+/// * `token_program` - SPL代币程序
+/// * `signer_seeds` - PDA签名种子
+/// * `amount` - 铸造数量
+/// 
+/// # 返回
+/// * `Result<()>` - 操作结果
+pub fn token_mint_with_signer<'info>(
+    mint: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+    signer_seeds: &[&[&[u8]]],
+    amount: u64,
+) -> Result<()> {
+    // 创建带签名的CPI上下文
+    let cpi_ctx: CpiContext<_> = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        token::MintTo {
+            mint,
+            to,
+            authority,
+        },
+        signer_seeds,
+    );
+
+    // 执行代币铸造
+    token::mint_to(cpi_ctx, amount)?;
+    Ok(())
+}
+
+/// 从用户账户销毁代币
+/// 
+/// 由代币持有者签名，从其代币账户中销毁代币
+/// 
+/// # 参数
+/// * `mint` - 代币铸造账户
+/// * `from` - 要销毁代币的账户
+/// * `authority` - 代币持有者（签名者）
+/// * `token_program` - SPL代币程序
+/// * `amount` - 销毁数量
+/// 
+/// # 返回
+/// * `Result<()>` - 操作结果
+pub fn token_burn_user<'info>(
+    mint: AccountInfo<'info>,
+    from: AccountInfo<'info>,
+    authority: &Signer<'info>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    // 创建CPI上下文
+    let cpi_ctx: CpiContext<_> = CpiContext::new(
+        token_program.to_account_info(),
+        token::Burn {
+            mint,
+            from,
+            authority: authority.to_account_info(),
+        },
+    );
+
+    // 执行代币销毁
+    token::burn(cpi_ctx, amount)?;
+    Ok(())
+}
+
 /// 计算基点（BPS）乘法
 /// 
 /// 用于计算手续费等基于基点的计算
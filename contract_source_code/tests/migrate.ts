@@ -0,0 +1,197 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN, Program } from "@coral-xyz/anchor";
+import {
+  getAccount,
+  getAssociatedTokenAddressSync,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import {
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+} from "@solana/web3.js";
+import { expect } from "chai";
+import { randomBytes } from "crypto";
+import { PredictionMarket } from "../target/types/prediction_market";
+import { MockAmm } from "../target/types/mock_amm";
+
+// 毕业迁移集成测试：曲线完成 → 经迁移托管CPI到本地测试用AMM建池 → 标记已迁移 → 重复迁移被拒绝
+describe("migrate", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.PredictionMarket as Program<PredictionMarket>;
+  const amm = anchor.workspace.MockAmm as Program<MockAmm>;
+  const authority = provider.wallet.publicKey;
+  const teamWallet = Keypair.generate().publicKey;
+
+  const pda = (seeds: (Buffer | Uint8Array)[], programId = program.programId) =>
+    PublicKey.findProgramAddressSync(seeds, programId)[0];
+
+  const marketId = randomBytes(32);
+  const yesToken = pda([Buffer.from("yes"), marketId]);
+  const noToken = pda([Buffer.from("no"), marketId]);
+  const market = pda([Buffer.from("market"), yesToken.toBuffer(), noToken.toBuffer()]);
+  const globalVault = pda([Buffer.from("global")]);
+  const migrationAuthority = pda([Buffer.from("migration"), market.toBuffer()]);
+  const pool = pda([Buffer.from("pool"), yesToken.toBuffer()], amm.programId);
+  const poolVault = pda([Buffer.from("pool_vault"), yesToken.toBuffer()], amm.programId);
+
+  const anyAmount = { range: { min: null, max: null } };
+
+  const migrate = () =>
+    program.methods
+      .migrate(0)
+      .accountsPartial({
+        market,
+        yesToken,
+        noToken,
+        authority,
+        ammProgram: amm.programId,
+      })
+      .remainingAccounts([
+        { pubkey: pool, isSigner: false, isWritable: true },
+        { pubkey: poolVault, isSigner: false, isWritable: true },
+        { pubkey: authority, isSigner: true, isWritable: true },
+      ])
+      .rpc();
+
+  before(async () => {
+    await program.methods
+      .configure({
+        authority,
+        pendingAuthority: PublicKey.default,
+        feeAdmin: PublicKey.default,
+        resolver: PublicKey.default,
+        pauser: PublicKey.default,
+        whitelistManager: PublicKey.default,
+        treasury: PublicKey.default,
+        pendingFeeAdmin: PublicKey.default,
+        pendingResolver: PublicKey.default,
+        pendingPauser: PublicKey.default,
+        pendingWhitelistManager: PublicKey.default,
+        pendingTreasury: PublicKey.default,
+        teamWallet,
+        feeRecipients: [],
+        platformBuyFee: new BN(0),
+        platformSellFee: new BN(0),
+        lpBuyFee: new BN(0),
+        lpSellFee: new BN(0),
+        platformBuyFeeConfig: anyAmount,
+        platformSellFeeConfig: anyAmount,
+        lpBuyFeeConfig: anyAmount,
+        lpSellFeeConfig: anyAmount,
+        creatorFeeConfig: anyAmount,
+        tokenSupplyConfig: new BN(1_000_000_000),
+        tokenDecimalsConfig: 9,
+        initialRealTokenReservesConfig: new BN(800_000_000),
+        initialVirtualSolReservesConfig: new BN(30 * LAMPORTS_PER_SOL),
+        initialVirtualTokenReservesConfig: new BN(1_073_000_000),
+        curveLimit: new BN(LAMPORTS_PER_SOL),
+        minSolLiquidity: new BN(0),
+        ammProgram: amm.programId,
+        whitelistEnabled: false,
+        marketCreationFee: new BN(0),
+        whitelistFeeWaiver: false,
+        creatorBond: new BN(0),
+        configTimelockSlots: new BN(0),
+        initialized: true,
+        paused: false,
+      })
+      .accountsPartial({ payer: authority })
+      .rpc();
+
+    await program.methods
+      .createMarket({
+        marketId: Array.from(marketId),
+        yesSymbol: "YES",
+        yesUri: "",
+        noSymbol: "NO",
+        noUri: "",
+        question: "Will the migrate test pass?",
+        rulesHash: Array.from(randomBytes(32)),
+        category: "test",
+        resolutionSource: "",
+        lpBuyFee: null,
+        lpSellFee: null,
+        creatorFee: null,
+        startSlot: null,
+        endingSlot: null,
+        startTimestamp: null,
+        endingTimestamp: null,
+        resolutionTimestamp: null,
+      })
+      .accountsPartial({
+        creator: authority,
+        yesToken,
+        noToken,
+        whitelist: null,
+        teamWallet,
+      })
+      .rpc();
+
+    // 买入超过毕业阈值的SOL，使YES曲线完成
+    await program.methods
+      .swap(new BN(1.2 * LAMPORTS_PER_SOL), 0, 0, new BN(0))
+      .accountsPartial({ market, yesToken, noToken, user: authority })
+      .rpc();
+  });
+
+  it("seeds the pool and marks the side as migrated", async () => {
+    const before = await program.account.market.fetch(market);
+    expect(before.isYesCurveCompleted).to.equal(true);
+    expect(before.isYesMigrated).to.equal(false);
+
+    const globalYesAta = getAssociatedTokenAddressSync(yesToken, globalVault, true);
+    const tokenAmount = (await getAccount(provider.connection, globalYesAta)).amount;
+    const solAmount = before.realYesSolReserves;
+
+    await migrate();
+
+    const poolState = await amm.account.pool.fetch(pool);
+    expect(poolState.mint.toBase58()).to.equal(yesToken.toBase58());
+    expect(poolState.tokenReserve.toString()).to.equal(tokenAmount.toString());
+    expect(poolState.solReserve.toString()).to.equal(solAmount.toString());
+
+    const vault = await getAccount(provider.connection, poolVault);
+    expect(vault.amount.toString()).to.equal(tokenAmount.toString());
+    expect((await getAccount(provider.connection, globalYesAta)).amount).to.equal(0n);
+
+    // 迁移托管只做中转，不留存资金
+    const escrowAta = getAssociatedTokenAddressSync(yesToken, migrationAuthority, true);
+    expect((await getAccount(provider.connection, escrowAta)).amount).to.equal(0n);
+    expect(await provider.connection.getBalance(migrationAuthority)).to.equal(0);
+
+    const after = await program.account.market.fetch(market);
+    expect(after.isYesMigrated).to.equal(true);
+    expect(after.isNoMigrated).to.equal(false);
+    expect(after.realYesSolReserves.toNumber()).to.equal(0);
+  });
+
+  it("rejects a second migration of the same side", async () => {
+    try {
+      await migrate();
+      expect.fail("second migrate should fail");
+    } catch (err) {
+      expect(String(err)).to.contain("AmmAlreadyExists");
+    }
+  });
+
+  it("only lets the configured AMM program be invoked", async () => {
+    try {
+      await program.methods
+        .migrate(0)
+        .accountsPartial({
+          market,
+          yesToken,
+          noToken,
+          authority,
+          ammProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      expect.fail("migrate with a foreign program should fail");
+    } catch (err) {
+      expect(String(err)).to.contain("InvalidMeteoraProgram");
+    }
+  });
+});
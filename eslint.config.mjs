@@ -12,6 +12,8 @@ const eslintConfig = defineConfig([
     "out/**",
     "build/**",
     "next-env.d.ts",
+    // 合约及其 Anchor 集成测试有独立的工具链
+    "contract_source_code/**",
   ]),
 ]);
 
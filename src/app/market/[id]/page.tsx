@@ -130,16 +130,13 @@ export default function MarketDetailPage() {
           window.location.href = paywallUrl;
           return;
         } else {
-          // Original contract-based swap (currently not working)
-          const teamWallet = market.creator.toString();
-
+          // Original contract-based swap
           const signature = await buyTokens(
             market.id,
             market.yesTokenMint.toString(),
             market.noTokenMint.toString(),
             selectedOutcome,
-            amount,
-            teamWallet
+            amount
           );
 
           setTxSignature(signature);
@@ -168,24 +165,20 @@ export default function MarketDetailPage() {
     const amount = parseFloat(liquidityAmount);
     if (amount > 0 && wallet.publicKey) {
       try {
-        const teamWallet = market.creator.toString();
-
         let signature;
         if (liquidityAction === "add") {
           signature = await addLiquidity(
             market.id,
             market.yesTokenMint.toString(),
             market.noTokenMint.toString(),
-            amount,
-            teamWallet
+            amount
           );
         } else {
           signature = await withdrawLiquidity(
             market.id,
             market.yesTokenMint.toString(),
             market.noTokenMint.toString(),
-            amount,
-            teamWallet
+            amount
           );
         }
 
@@ -4,8 +4,15 @@ import { PublicKey, Keypair, SystemProgram, SYSVAR_RENT_PUBKEY } from '@solana/w
 import { BN } from '@coral-xyz/anchor';
 import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID } from '@solana/spl-token';
 import { useSolanaProgram } from './useSolanaProgram';
-import { mintNoTokenRpc, createMarketRpc } from '@/lib/solana/client';
-import { solToLamports, DEPLOYED_CONFIG } from '@/lib/solana/config';
+import { createMarketRpc } from '@/lib/solana/client';
+import { CreateMarketParams } from '@/lib/solana/types';
+import {
+  solToLamports,
+  DEPLOYED_CONFIG,
+  getYesMintPDA,
+  getNoMintPDA,
+  computeMarketId,
+} from '@/lib/solana/config';
 
 export function useCreateMarket() {
   const wallet = useWallet();
@@ -29,16 +36,18 @@ export function useCreateMarket() {
       setLoading(true);
       setError(null);
 
-      // Generate new mint keypairs for Yes and No tokens
-      const yesTokenMint = Keypair.generate();
-      const noTokenMint = Keypair.generate();
+      // The full resolution rules live off-chain; the market stores their hash
+      const rulesHash = new Uint8Array(
+        await crypto.subtle.digest('SHA-256', new TextEncoder().encode(description))
+      );
 
-      // Convert end date to slot (approximate - 1 slot = ~400ms on Solana)
-      // For simplicity, we'll use current slot + (days * 216000)
-      const currentSlot = await connection.getSlot();
-      const endingSlot = endDate
-        ? currentSlot + Math.floor((endDate.getTime() / 1000 - Date.now() / 1000)) * 2.5 // ~2.5 slots per second
-        : 0; // 0 means no end date
+      // The market id is fixed by the question and rules; the YES/NO mints are PDAs derived from it
+      const marketId = await computeMarketId(question, rulesHash);
+      const [yesTokenMint] = getYesMintPDA(marketId);
+      const [noTokenMint] = getNoMintPDA(marketId);
+
+      // End of trading as a Unix timestamp (seconds)
+      const endingTimestamp = endDate ? new BN(Math.floor(endDate.getTime() / 1000)) : null;
 
       // Convert initial liquidity to lamports
       const initialLiquidity = new BN(solToLamports(initialLiquiditySol));
@@ -66,41 +75,35 @@ export function useCreateMarket() {
         console.log('Global config found:', globalConfigPDA.toString());
         console.log('Config account owner:', configAccount.owner.toString());
 
-        console.log('Step 1: Minting NO token...');
-        console.log('NO Token Mint:', noTokenMint.publicKey.toString());
+        console.log('Creating market...');
+        console.log('YES Token Mint:', yesTokenMint.toString());
+        console.log('NO Token Mint:', noTokenMint.toString());
         console.log('Team Wallet:', teamWallet.toString());
         console.log('Creator:', wallet.publicKey.toString());
 
-        // Step 1: Mint the NO token first (using RPC - handles signing automatically)
-        const noTokenSignature = await mintNoTokenRpc(
-          provider,
-          noTokenMint,
-          noSymbol,
-          '' // Empty URI for now
-        );
-
-        console.log('NO token transaction sent:', noTokenSignature);
-        await connection.confirmTransaction(noTokenSignature, 'confirmed');
-        console.log('NO token minted successfully');
-
-        console.log('Step 2: Creating market...');
-        // Step 2: Create the market with both tokens
-        // Construct CreateMarketParams
-        const params = {
+        // YES/NO mints, metadata and the market are created in one instruction
+        const params: CreateMarketParams = {
+          marketId: Array.from(marketId),
           yesSymbol,
           yesUri: '', // Empty URI for now
+          noSymbol,
+          noUri: '', // Empty URI for now
+          question,
+          rulesHash: Array.from(rulesHash),
+          category,
+          resolutionSource: '',
+          lpBuyFee: null, // Use the config defaults
+          lpSellFee: null,
+          creatorFee: null,
           startSlot: null, // Start immediately
-          endingSlot: endDate ? new BN(endingSlot) : null,
+          endingSlot: null,
+          startTimestamp: null,
+          endingTimestamp,
+          resolutionTimestamp: null,
         };
 
         // Create market (using RPC - handles signing automatically)
-        signature = await createMarketRpc(
-          provider,
-          yesTokenMint, // Pass the Keypair for YES token
-          noTokenMint.publicKey, // Pass the PublicKey for NO token (already minted)
-          params,
-          teamWallet
-        );
+        signature = await createMarketRpc(provider, params, teamWallet);
 
         console.log('Market creation transaction sent:', signature);
         await connection.confirmTransaction(signature, 'confirmed');
@@ -123,8 +126,8 @@ export function useCreateMarket() {
 
       return {
         signature,
-        yesTokenMint: yesTokenMint.publicKey.toString(),
-        noTokenMint: noTokenMint.publicKey.toString(),
+        yesTokenMint: yesTokenMint.toString(),
+        noTokenMint: noTokenMint.toString(),
       };
     } catch (err) {
       console.error('Create market error:', err);
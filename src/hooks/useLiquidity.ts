@@ -17,8 +17,7 @@ export function useLiquidity() {
     marketAddress: string,
     yesTokenMint: string,
     noTokenMint: string,
-    solAmount: number,
-    teamWallet: string
+    solAmount: number
   ) => {
     if (!wallet.publicKey || !provider) {
       throw new Error('Wallet not connected');
@@ -31,7 +30,6 @@ export function useLiquidity() {
       const marketPubkey = new PublicKey(marketAddress);
       const yesTokenPubkey = new PublicKey(yesTokenMint);
       const noTokenPubkey = new PublicKey(noTokenMint);
-      const teamWalletPubkey = new PublicKey(teamWallet);
 
       const amount = new BN(solToLamports(solAmount));
 
@@ -46,8 +44,7 @@ export function useLiquidity() {
         marketPubkey,
         yesTokenPubkey,
         noTokenPubkey,
-        amount,
-        teamWalletPubkey
+        amount
       );
 
       tx.feePayer = wallet.publicKey;
@@ -87,8 +84,7 @@ export function useLiquidity() {
     marketAddress: string,
     yesTokenMint: string,
     noTokenMint: string,
-    solAmount: number,
-    teamWallet: string
+    solAmount: number
   ) => {
     if (!wallet.publicKey || !provider) {
       throw new Error('Wallet not connected');
@@ -101,7 +97,6 @@ export function useLiquidity() {
       const marketPubkey = new PublicKey(marketAddress);
       const yesTokenPubkey = new PublicKey(yesTokenMint);
       const noTokenPubkey = new PublicKey(noTokenMint);
-      const teamWalletPubkey = new PublicKey(teamWallet);
 
       const amount = new BN(solToLamports(solAmount));
 
@@ -116,8 +111,7 @@ export function useLiquidity() {
         marketPubkey,
         yesTokenPubkey,
         noTokenPubkey,
-        amount,
-        teamWalletPubkey
+        amount
       );
 
       tx.feePayer = wallet.publicKey;
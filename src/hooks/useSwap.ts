@@ -19,8 +19,7 @@ export function useSwap() {
     yesTokenMint: string,
     noTokenMint: string,
     tokenType: 'yes' | 'no',
-    solAmount: number, // Amount of SOL to spend
-    teamWallet: string
+    solAmount: number // Amount of SOL to spend
   ) => {
     if (!wallet.publicKey || !provider) {
       throw new Error('Wallet not connected');
@@ -33,7 +32,6 @@ export function useSwap() {
       const marketPubkey = new PublicKey(marketAddress);
       const yesTokenPubkey = new PublicKey(yesTokenMint);
       const noTokenPubkey = new PublicKey(noTokenMint);
-      const teamWalletPubkey = new PublicKey(teamWallet);
 
       // Convert SOL to lamports
       const amount = new BN(solToLamports(solAmount));
@@ -57,8 +55,7 @@ export function useSwap() {
         amount,
         TradeDirection.Buy,
         tokenType === 'yes' ? TokenType.Yes : TokenType.No,
-        minimumReceiveAmount,
-        teamWalletPubkey
+        minimumReceiveAmount
       );
 
       console.log('✅ Swap transaction created, preparing to send...');
@@ -122,8 +119,7 @@ export function useSwap() {
     yesTokenMint: string,
     noTokenMint: string,
     tokenType: 'yes' | 'no',
-    tokenAmount: number, // Amount of tokens to sell
-    teamWallet: string
+    tokenAmount: number // Amount of tokens to sell
   ) => {
     if (!wallet.publicKey || !provider) {
       throw new Error('Wallet not connected');
@@ -136,7 +132,6 @@ export function useSwap() {
       const marketPubkey = new PublicKey(marketAddress);
       const yesTokenPubkey = new PublicKey(yesTokenMint);
       const noTokenPubkey = new PublicKey(noTokenMint);
-      const teamWalletPubkey = new PublicKey(teamWallet);
 
       // Convert token amount (assuming 9 decimals)
       const amount = new BN(tokenAmount * 1e9);
@@ -153,8 +148,7 @@ export function useSwap() {
         amount,
         TradeDirection.Sell,
         tokenType === 'yes' ? TokenType.Yes : TokenType.No,
-        minimumReceiveAmount,
-        teamWalletPubkey
+        minimumReceiveAmount
       );
 
       // Send transaction
@@ -4,7 +4,6 @@ import {
   Transaction,
   SystemProgram,
   SYSVAR_RENT_PUBKEY,
-  Keypair,
 } from '@solana/web3.js';
 import { Program, AnchorProvider, BN } from '@coral-xyz/anchor';
 import {
@@ -20,6 +19,14 @@ import {
   getGlobalVaultPDA,
   getMarketPDA,
   getUserInfoPDA,
+  getYesMintPDA,
+  getNoMintPDA,
+  getLpMintPDA,
+  getLpPositionPDA,
+  getFeeVaultPDA,
+  getMarketMetadataPDA,
+  getCreatorBondPDA,
+  getWhitelistPDA,
   connection,
   fromTokenAmount,
   lamportsToSol,
@@ -237,162 +244,64 @@ export async function fetchTokenMetadata(mint: PublicKey): Promise<{ name: strin
 }
 
 /**
- * Create a transaction to mint NO token
- * This must be called before creating a market
+ * Resolve the accounts for create_market
+ * YES/NO mints are PDAs derived from the market id, so no mint keypairs are needed
  */
-export async function createMintNoTokenInstruction(
+async function getCreateMarketAccounts(
   provider: AnchorProvider,
-  noTokenKeypair: Keypair,
-  noSymbol: string,
-  noUri: string
-): Promise<Transaction> {
-  const program = getProgram(provider);
+  params: CreateMarketParams,
+  teamWallet: PublicKey
+) {
+  const marketId = Uint8Array.from(params.marketId);
   const [globalConfig] = getGlobalConfigPDA();
   const [globalVault] = getGlobalVaultPDA();
-
-  const noTokenMetadata = getMetadataPDA(noTokenKeypair.publicKey);
-  const globalNoTokenAccount = await getAssociatedTokenAddress(
-    noTokenKeypair.publicKey,
+  const [yesToken] = getYesMintPDA(marketId);
+  const [noToken] = getNoMintPDA(marketId);
+  const [market] = getMarketPDA(yesToken, noToken);
+  const [marketMetadata] = getMarketMetadataPDA(market);
+  const [bondEscrow] = getCreatorBondPDA(market);
+
+  // Only whitelisted creators have a whitelist entry; the account is optional otherwise
+  const [whitelistPDA] = getWhitelistPDA(provider.wallet.publicKey);
+  const whitelist = (await provider.connection.getAccountInfo(whitelistPDA)) ? whitelistPDA : null;
+
+  return {
+    globalConfig,
     globalVault,
-    true
-  );
-
-  const tx = await program.methods
-    .mintNoToken(noSymbol, noUri)
-    .accounts({
-      globalConfig,
-      globalVault,
-      creator: provider.wallet.publicKey,
-      noToken: noTokenKeypair.publicKey,
-      noTokenMetadataAccount: noTokenMetadata,
-      globalNoTokenAccount,
-      systemProgram: SystemProgram.programId,
-      rent: SYSVAR_RENT_PUBKEY,
-      tokenProgram: TOKEN_PROGRAM_ID,
-      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-      mplTokenMetadataProgram: METADATA_PROGRAM_ID,
-    })
-    .transaction();
-
-  return tx;
+    creator: provider.wallet.publicKey,
+    whitelist,
+    yesToken,
+    noToken,
+    market,
+    marketMetadata,
+    bondEscrow,
+    yesTokenMetadataAccount: getMetadataPDA(yesToken),
+    noTokenMetadataAccount: getMetadataPDA(noToken),
+    globalYesTokenAccount: await getAssociatedTokenAddress(yesToken, globalVault, true),
+    globalNoTokenAccount: await getAssociatedTokenAddress(noToken, globalVault, true),
+    systemProgram: SystemProgram.programId,
+    rent: SYSVAR_RENT_PUBKEY,
+    tokenProgram: TOKEN_PROGRAM_ID,
+    associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+    mplTokenMetadataProgram: METADATA_PROGRAM_ID,
+    teamWallet,
+  };
 }
 
 /**
- * Mint NO token using RPC (handles signing and sending automatically)
- */
-export async function mintNoTokenRpc(
-  provider: AnchorProvider,
-  noTokenKeypair: Keypair,
-  noSymbol: string,
-  noUri: string
-): Promise<string> {
-  const program = getProgram(provider);
-  const [globalConfig] = getGlobalConfigPDA();
-  const [globalVault] = getGlobalVaultPDA();
-
-  const noTokenMetadata = getMetadataPDA(noTokenKeypair.publicKey);
-  const globalNoTokenAccount = await getAssociatedTokenAddress(
-    noTokenKeypair.publicKey,
-    globalVault,
-    true
-  );
-
-  // Log accounts for debugging
-  console.log('Mint NO Token accounts:', {
-    globalConfig: globalConfig.toString(),
-    globalVault: globalVault.toString(),
-    creator: provider.wallet.publicKey.toString(),
-    noToken: noTokenKeypair.publicKey.toString(),
-  });
-
-  try {
-    const tx = await program.methods
-      .mintNoToken(noSymbol, noUri)
-      .accounts({
-        globalConfig,
-        globalVault,
-        creator: provider.wallet.publicKey,
-        noToken: noTokenKeypair.publicKey,
-        noTokenMetadataAccount: noTokenMetadata,
-        globalNoTokenAccount,
-        systemProgram: SystemProgram.programId,
-        rent: SYSVAR_RENT_PUBKEY,
-        tokenProgram: TOKEN_PROGRAM_ID,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-        mplTokenMetadataProgram: METADATA_PROGRAM_ID,
-      })
-      .transaction();
-
-    // Set recent blockhash and fee payer
-    const { blockhash } = await provider.connection.getLatestBlockhash();
-    tx.recentBlockhash = blockhash;
-    tx.feePayer = provider.wallet.publicKey;
-
-    // Sign with the noToken keypair first
-    tx.partialSign(noTokenKeypair);
-
-    // Sign with wallet (this will prompt user)
-    const signedTx = await provider.wallet.signTransaction(tx);
-
-    // Send the fully signed transaction
-    const signature = await provider.connection.sendRawTransaction(signedTx.serialize(), {
-      skipPreflight: true,
-      preflightCommitment: 'confirmed',
-    });
-
-    // Wait for confirmation
-    await provider.connection.confirmTransaction(signature, 'confirmed');
-
-    return signature;
-  } catch (error) {
-    console.error('mintNoTokenRpc error:', error);
-    throw error;
-  }
-}
-
-/**
- * Create a transaction to create a new market
+ * Create a transaction to create a new market (YES and NO tokens are minted atomically)
  */
 export async function createMarketInstruction(
   provider: AnchorProvider,
-  yesTokenKeypair: Keypair,
-  noTokenMint: PublicKey,
   params: CreateMarketParams,
   teamWallet: PublicKey
 ): Promise<Transaction> {
   const program = getProgram(provider);
-  const [globalConfig] = getGlobalConfigPDA();
-  const [globalVault] = getGlobalVaultPDA();
-  const [market] = getMarketPDA(yesTokenKeypair.publicKey, noTokenMint);
-
-  const yesTokenMetadata = getMetadataPDA(yesTokenKeypair.publicKey);
-  const noTokenMetadata = getMetadataPDA(noTokenMint);
-
-  const globalYesTokenAccount = await getAssociatedTokenAddress(
-    yesTokenKeypair.publicKey,
-    globalVault,
-    true
-  );
+  const accounts = await getCreateMarketAccounts(provider, params, teamWallet);
 
   const tx = await program.methods
     .createMarket(params)
-    .accounts({
-      globalConfig,
-      globalVault,
-      creator: provider.wallet.publicKey,
-      yesToken: yesTokenKeypair.publicKey,
-      noToken: noTokenMint,
-      market,
-      yesTokenMetadataAccount: yesTokenMetadata,
-      noTokenMetadataAccount: noTokenMetadata,
-      globalYesTokenAccount,
-      systemProgram: SystemProgram.programId,
-      rent: SYSVAR_RENT_PUBKEY,
-      tokenProgram: TOKEN_PROGRAM_ID,
-      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-      mplTokenMetadataProgram: METADATA_PROGRAM_ID,
-      teamWallet,
-    })
+    .accountsPartial(accounts)
     .transaction();
 
   return tx;
@@ -403,45 +312,24 @@ export async function createMarketInstruction(
  */
 export async function createMarketRpc(
   provider: AnchorProvider,
-  yesTokenKeypair: Keypair,
-  noTokenMint: PublicKey,
   params: CreateMarketParams,
   teamWallet: PublicKey
 ): Promise<string> {
   const program = getProgram(provider);
-  const [globalConfig] = getGlobalConfigPDA();
-  const [globalVault] = getGlobalVaultPDA();
-  const [market] = getMarketPDA(yesTokenKeypair.publicKey, noTokenMint);
-
-  const yesTokenMetadata = getMetadataPDA(yesTokenKeypair.publicKey);
-  const noTokenMetadata = getMetadataPDA(noTokenMint);
-
-  const globalYesTokenAccount = await getAssociatedTokenAddress(
-    yesTokenKeypair.publicKey,
-    globalVault,
-    true
-  );
+  const accounts = await getCreateMarketAccounts(provider, params, teamWallet);
+
+  console.log('Create market accounts:', {
+    globalConfig: accounts.globalConfig.toString(),
+    market: accounts.market.toString(),
+    yesToken: accounts.yesToken.toString(),
+    noToken: accounts.noToken.toString(),
+    creator: accounts.creator.toString(),
+  });
 
   try {
     const tx = await program.methods
       .createMarket(params)
-      .accounts({
-        globalConfig,
-        globalVault,
-        creator: provider.wallet.publicKey,
-        yesToken: yesTokenKeypair.publicKey,
-        noToken: noTokenMint,
-        market,
-        yesTokenMetadataAccount: yesTokenMetadata,
-        noTokenMetadataAccount: noTokenMetadata,
-        globalYesTokenAccount,
-        systemProgram: SystemProgram.programId,
-        rent: SYSVAR_RENT_PUBKEY,
-        tokenProgram: TOKEN_PROGRAM_ID,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-        mplTokenMetadataProgram: METADATA_PROGRAM_ID,
-        teamWallet,
-      })
+      .accountsPartial(accounts)
       .transaction();
 
     // Set recent blockhash and fee payer
@@ -449,9 +337,6 @@ export async function createMarketRpc(
     tx.recentBlockhash = blockhash;
     tx.feePayer = provider.wallet.publicKey;
 
-    // Sign with the yesToken keypair first
-    tx.partialSign(yesTokenKeypair);
-
     // Sign with wallet (this will prompt user)
     const signedTx = await provider.wallet.signTransaction(tx);
 
@@ -482,12 +367,12 @@ export async function createSwapInstruction(
   amount: BN,
   direction: TradeDirection,
   tokenType: TokenType,
-  minimumReceiveAmount: BN,
-  teamWallet: PublicKey
+  minimumReceiveAmount: BN
 ): Promise<Transaction> {
   const program = getProgram(provider);
   const [globalConfig] = getGlobalConfigPDA();
   const [globalVault] = getGlobalVaultPDA();
+  const [feeVault] = getFeeVaultPDA();
   const [userInfo] = getUserInfoPDA(provider.wallet.publicKey, marketPubkey);
 
   const globalYesAta = await getAssociatedTokenAddress(
@@ -549,7 +434,7 @@ export async function createSwapInstruction(
     .swap(amount, direction, tokenType, minimumReceiveAmount)
     .accounts({
       globalConfig,
-      teamWallet,
+      feeVault,
       market: marketPubkey,
       globalVault,
       yesToken: yesTokenMint,
@@ -573,30 +458,36 @@ export async function createSwapInstruction(
 
 /**
  * Create a transaction to add liquidity to a market
+ * The LP share tokens are held in custody and credited to the caller's LP position;
+ * use createUnstakeLpInstruction to move them to the wallet
  */
 export async function createAddLiquidityInstruction(
   provider: AnchorProvider,
   marketPubkey: PublicKey,
   yesTokenMint: PublicKey,
   noTokenMint: PublicKey,
-  amount: BN,
-  teamWallet: PublicKey
+  amount: BN
 ): Promise<Transaction> {
   const program = getProgram(provider);
   const [globalConfig] = getGlobalConfigPDA();
   const [globalVault] = getGlobalVaultPDA();
+  const [lpMint] = getLpMintPDA(marketPubkey);
   const [userInfo] = getUserInfoPDA(provider.wallet.publicKey, marketPubkey);
+  const [lpPosition] = getLpPositionPDA(marketPubkey, provider.wallet.publicKey);
+  const lpCustody = await getAssociatedTokenAddress(lpMint, globalVault, true);
 
   const tx = await program.methods
     .addLiquidity(amount)
-    .accounts({
+    .accountsPartial({
       globalConfig,
-      teamWallet,
       market: marketPubkey,
       globalVault,
       yesToken: yesTokenMint,
       noToken: noTokenMint,
+      lpMint,
+      lpCustody,
       userInfo,
+      lpPosition,
       user: provider.wallet.publicKey,
       systemProgram: SystemProgram.programId,
       tokenProgram: TOKEN_PROGRAM_ID,
@@ -609,31 +500,192 @@ export async function createAddLiquidityInstruction(
 
 /**
  * Create a transaction to withdraw liquidity from a market
+ * `amount` is the SOL value to redeem; it is converted to LP share tokens to burn
+ * (capped at the shares in the user's LP position)
  */
 export async function createWithdrawLiquidityInstruction(
   provider: AnchorProvider,
   marketPubkey: PublicKey,
   yesTokenMint: PublicKey,
   noTokenMint: PublicKey,
-  amount: BN,
-  teamWallet: PublicKey
+  amount: BN
 ): Promise<Transaction> {
   const program = getProgram(provider);
   const [globalConfig] = getGlobalConfigPDA();
   const [globalVault] = getGlobalVaultPDA();
+  const [lpMint] = getLpMintPDA(marketPubkey);
   const [userInfo] = getUserInfoPDA(provider.wallet.publicKey, marketPubkey);
+  const user = provider.wallet.publicKey;
+  const [lpPosition] = getLpPositionPDA(marketPubkey, user);
+  const lpCustody = await getAssociatedTokenAddress(lpMint, globalVault, true);
+
+  // Each LP share is worth total_lp_amount / total_lp_shares (LP fees are claimed separately)
+  const market: any = await program.account.market.fetch(marketPubkey);
+  if (market.totalLpAmount.isZero()) {
+    throw new Error('Market has no liquidity to withdraw');
+  }
+  const position: any = await program.account.lpPosition.fetch(lpPosition);
+  const requested = amount.mul(market.totalLpShares).div(market.totalLpAmount);
+  const shares = BN.min(requested, position.shares);
 
   const tx = await program.methods
-    .withdrawLiquidity(amount)
-    .accounts({
+    .withdrawLiquidity(shares)
+    .accountsPartial({
       globalConfig,
-      teamWallet,
       market: marketPubkey,
       globalVault,
       yesToken: yesTokenMint,
       noToken: noTokenMint,
+      globalYesAta: await getAssociatedTokenAddress(yesTokenMint, globalVault, true),
+      globalNoAta: await getAssociatedTokenAddress(noTokenMint, globalVault, true),
+      userYesAta: await getAssociatedTokenAddress(yesTokenMint, user),
+      userNoAta: await getAssociatedTokenAddress(noTokenMint, user),
+      lpMint,
+      lpCustody,
+      lpPosition,
       userInfo,
-      user: provider.wallet.publicKey,
+      user,
+      systemProgram: SystemProgram.programId,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+    })
+    .transaction();
+
+  return tx;
+}
+
+/**
+ * Create a transaction to claim the LP fees accrued by the caller's LP position
+ */
+export async function createClaimLpFeesInstruction(
+  provider: AnchorProvider,
+  marketPubkey: PublicKey,
+  yesTokenMint: PublicKey,
+  noTokenMint: PublicKey
+): Promise<Transaction> {
+  const program = getProgram(provider);
+  const [globalVault] = getGlobalVaultPDA();
+  const user = provider.wallet.publicKey;
+  const [lpPosition] = getLpPositionPDA(marketPubkey, user);
+
+  const tx = await program.methods
+    .claimLpFees()
+    .accountsPartial({
+      market: marketPubkey,
+      globalVault,
+      yesToken: yesTokenMint,
+      noToken: noTokenMint,
+      lpPosition,
+      user,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+
+  return tx;
+}
+
+/**
+ * Create a transaction to move LP share tokens from the wallet into the caller's LP position
+ * (needed before withdrawing shares received by transfer)
+ */
+export async function createStakeLpInstruction(
+  provider: AnchorProvider,
+  marketPubkey: PublicKey,
+  yesTokenMint: PublicKey,
+  noTokenMint: PublicKey,
+  shares: BN
+): Promise<Transaction> {
+  const program = getProgram(provider);
+  const [globalVault] = getGlobalVaultPDA();
+  const [lpMint] = getLpMintPDA(marketPubkey);
+  const user = provider.wallet.publicKey;
+  const [lpPosition] = getLpPositionPDA(marketPubkey, user);
+
+  const tx = await program.methods
+    .stakeLp(shares)
+    .accountsPartial({
+      market: marketPubkey,
+      globalVault,
+      yesToken: yesTokenMint,
+      noToken: noTokenMint,
+      lpMint,
+      lpCustody: await getAssociatedTokenAddress(lpMint, globalVault, true),
+      userLpAta: await getAssociatedTokenAddress(lpMint, user),
+      lpPosition,
+      user,
+      systemProgram: SystemProgram.programId,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+    })
+    .transaction();
+
+  return tx;
+}
+
+/**
+ * Create a transaction to move LP share tokens out of the caller's LP position into the wallet
+ */
+export async function createUnstakeLpInstruction(
+  provider: AnchorProvider,
+  marketPubkey: PublicKey,
+  yesTokenMint: PublicKey,
+  noTokenMint: PublicKey,
+  shares: BN
+): Promise<Transaction> {
+  const program = getProgram(provider);
+  const [globalVault] = getGlobalVaultPDA();
+  const [lpMint] = getLpMintPDA(marketPubkey);
+  const user = provider.wallet.publicKey;
+  const [lpPosition] = getLpPositionPDA(marketPubkey, user);
+
+  const tx = await program.methods
+    .unstakeLp(shares)
+    .accountsPartial({
+      market: marketPubkey,
+      globalVault,
+      yesToken: yesTokenMint,
+      noToken: noTokenMint,
+      lpMint,
+      lpCustody: await getAssociatedTokenAddress(lpMint, globalVault, true),
+      userLpAta: await getAssociatedTokenAddress(lpMint, user),
+      lpPosition,
+      user,
+      systemProgram: SystemProgram.programId,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+    })
+    .transaction();
+
+  return tx;
+}
+
+/**
+ * Create a transaction to redeem outcome tokens for SOL after the market is resolved
+ */
+export async function createRedeemInstruction(
+  provider: AnchorProvider,
+  marketPubkey: PublicKey,
+  yesTokenMint: PublicKey,
+  noTokenMint: PublicKey,
+  amount: BN,
+  tokenType: TokenType
+): Promise<Transaction> {
+  const program = getProgram(provider);
+  const [globalVault] = getGlobalVaultPDA();
+  const user = provider.wallet.publicKey;
+  const mint = tokenType === TokenType.Yes ? yesTokenMint : noTokenMint;
+
+  const tx = await program.methods
+    .redeem(amount, tokenType)
+    .accountsPartial({
+      market: marketPubkey,
+      globalVault,
+      yesToken: yesTokenMint,
+      noToken: noTokenMint,
+      tokenMint: mint,
+      globalAta: await getAssociatedTokenAddress(mint, globalVault, true),
+      userAta: await getAssociatedTokenAddress(mint, user),
+      user,
       systemProgram: SystemProgram.programId,
       tokenProgram: TOKEN_PROGRAM_ID,
       associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -14,6 +14,14 @@ export const CONFIG_SEED = Buffer.from('config');
 export const GLOBAL_VAULT_SEED = Buffer.from('global');
 export const MARKET_SEED = Buffer.from('market');
 export const USER_INFO_SEED = Buffer.from('userinfo');
+export const YES_MINT_SEED = Buffer.from('yes');
+export const NO_MINT_SEED = Buffer.from('no');
+export const LP_MINT_SEED = Buffer.from('lp_mint');
+export const LP_POSITION_SEED = Buffer.from('lp-position');
+export const FEE_VAULT_SEED = Buffer.from('fee-vault');
+export const MARKET_METADATA_SEED = Buffer.from('market-metadata');
+export const CREATOR_BOND_SEED = Buffer.from('creator_bond');
+export const WHITELIST_SEED = Buffer.from('wl-seed');
 
 // Get PDA for global config
 export function getGlobalConfigPDA(): [PublicKey, number] {
@@ -39,6 +47,57 @@ export function getMarketPDA(yesToken: PublicKey, noToken: PublicKey): [PublicKe
   );
 }
 
+// Market id required by the program: sha256(question bytes || rules hash)
+export async function computeMarketId(question: string, rulesHash: Uint8Array): Promise<Uint8Array> {
+  const questionBytes = new TextEncoder().encode(question);
+  const preimage = new Uint8Array(questionBytes.length + rulesHash.length);
+  preimage.set(questionBytes);
+  preimage.set(rulesHash, questionBytes.length);
+  return new Uint8Array(await crypto.subtle.digest('SHA-256', preimage));
+}
+
+// Get PDAs for the YES/NO mints (derived from the market id)
+export function getYesMintPDA(marketId: Uint8Array): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([YES_MINT_SEED, marketId], PROGRAM_ID);
+}
+
+export function getNoMintPDA(marketId: Uint8Array): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([NO_MINT_SEED, marketId], PROGRAM_ID);
+}
+
+// Get PDA for the market's LP share mint
+export function getLpMintPDA(market: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([LP_MINT_SEED, market.toBuffer()], PROGRAM_ID);
+}
+
+// Get PDA for an LP's position in a market
+export function getLpPositionPDA(market: PublicKey, lp: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [LP_POSITION_SEED, market.toBuffer(), lp.toBuffer()],
+    PROGRAM_ID
+  );
+}
+
+// Get PDA for the platform fee vault
+export function getFeeVaultPDA(): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([FEE_VAULT_SEED], PROGRAM_ID);
+}
+
+// Get PDA for market metadata
+export function getMarketMetadataPDA(market: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([MARKET_METADATA_SEED, market.toBuffer()], PROGRAM_ID);
+}
+
+// Get PDA for the creator bond escrow
+export function getCreatorBondPDA(market: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([CREATOR_BOND_SEED, market.toBuffer()], PROGRAM_ID);
+}
+
+// Get PDA for a creator's whitelist entry
+export function getWhitelistPDA(creator: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([WHITELIST_SEED, creator.toBuffer()], PROGRAM_ID);
+}
+
 // Get PDA for user info
 export function getUserInfoPDA(user: PublicKey, market: PublicKey): [PublicKey, number] {
   return PublicKey.findProgramAddressSync(
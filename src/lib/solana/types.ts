@@ -54,10 +54,23 @@ export interface GlobalConfigAccount {
 
 // Create Market Parameters
 export interface CreateMarketParams {
+  marketId: number[]; // 32 bytes, sha256(question || rulesHash); YES/NO mints are derived from it
   yesSymbol: string;
   yesUri: string;
+  noSymbol: string;
+  noUri: string;
+  question: string;
+  rulesHash: number[]; // 32 bytes
+  category: string;
+  resolutionSource: string;
+  lpBuyFee: BN | null;
+  lpSellFee: BN | null;
+  creatorFee: BN | null;
   startSlot: BN | null;
   endingSlot: BN | null;
+  startTimestamp: BN | null;
+  endingTimestamp: BN | null;
+  resolutionTimestamp: BN | null;
 }
 
 // Trade direction and token type enums